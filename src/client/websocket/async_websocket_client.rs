@@ -1,3 +1,4 @@
+use crate::core::io::timeout::{connect_timeout, Duration};
 use crate::{client::websocket::errors::WebsocketError, Err};
 
 use anyhow::Result;
@@ -141,13 +142,30 @@ impl
             WebsocketOpen,
         >,
     > {
-        let (websocket_stream, _) = tungstenite_connect_async(uri).await.unwrap();
+        let (websocket_stream, _) = match tungstenite_connect_async(uri).await {
+            Ok(connection) => connection,
+            Err(error) => return Err!(WebsocketError::from(error)),
+        };
 
         Ok(AsyncWebsocketClient {
             inner: websocket_stream,
             status: PhantomData::<WebsocketOpen>,
         })
     }
+
+    /// Like [`AsyncWebsocketClient::open`] but fails if the connection and the websocket
+    /// handshake did not complete within `open_timeout`.
+    pub async fn open_with_timeout(
+        uri: Url,
+        open_timeout: Duration,
+    ) -> Result<
+        AsyncWebsocketClient<
+            TungsteniteWebsocketStream<TungsteniteMaybeTlsStream<TcpStream>>,
+            WebsocketOpen,
+        >,
+    > {
+        connect_timeout(open_timeout, Self::open(uri)).await
+    }
 }
 
 impl<Rng>
@@ -169,16 +187,37 @@ where
     {
         let websocket = EmbeddedWebsocket::<Rng, EmbeddedWebsocketClient>::new_client(rng);
         let mut framer = EmbeddedWebsocketFramer::new(websocket);
-        framer
-            .connect(stream, buffer, websocket_options)
-            .await
-            .unwrap();
+        if let Err(error) = framer.connect(stream, buffer, websocket_options).await {
+            return Err!(WebsocketError::from(error));
+        }
 
         Ok(AsyncWebsocketClient {
             inner: framer,
             status: PhantomData::<WebsocketOpen>,
         })
     }
+
+    /// Like [`AsyncWebsocketClient::open`] but fails if the websocket handshake did not
+    /// complete within `open_timeout`.
+    pub async fn open_with_timeout<B, E>(
+        stream: &mut (impl Stream<Item = Result<B, E>> + for<'a> Sink<&'a [u8], Error = E> + Unpin),
+        buffer: &mut [u8],
+        rng: Rng,
+        websocket_options: &EmbeddedWebsocketOptions<'_>,
+        open_timeout: Duration,
+    ) -> Result<
+        AsyncWebsocketClient<EmbeddedWebsocketFramer<Rng, EmbeddedWebsocketClient>, WebsocketOpen>,
+    >
+    where
+        B: AsRef<[u8]>,
+        E: Debug,
+    {
+        connect_timeout(
+            open_timeout,
+            Self::open(stream, buffer, rng, websocket_options),
+        )
+        .await
+    }
 }
 
 impl<Rng> AsyncWebsocketClient<EmbeddedWebsocketFramer<Rng, EmbeddedWebsocketClient>, WebsocketOpen>
@@ -195,12 +234,13 @@ where
     where
         E: Debug,
     {
-        let len = self
+        match self
             .inner
             .encode::<E>(message_type, end_of_message, from, to)
-            .unwrap();
-
-        Ok(len)
+        {
+            Ok(len) => Ok(len),
+            Err(error) => Err!(WebsocketError::from(error)),
+        }
     }
 
     pub async fn send<'b, E>(
@@ -214,12 +254,14 @@ where
     where
         E: Debug,
     {
-        self.inner
+        match self
+            .inner
             .write(stream, stream_buf, message_type, end_of_message, frame_buf)
             .await
-            .unwrap();
-
-        Ok(())
+        {
+            Ok(()) => Ok(()),
+            Err(error) => Err!(WebsocketError::from(error)),
+        }
     }
 
     pub async fn close<'b, E>(
@@ -232,12 +274,14 @@ where
     where
         E: Debug,
     {
-        self.inner
+        match self
+            .inner
             .close(stream, stream_buf, close_status, status_description)
             .await
-            .unwrap();
-
-        Ok(())
+        {
+            Ok(()) => Ok(()),
+            Err(error) => Err!(WebsocketError::from(error)),
+        }
     }
 
     pub async fn next<'a, B: Deref<Target = [u8]>, E>(
@@ -1,4 +1,5 @@
-use super::async_websocket_client::EmbeddedWebsocketFramerError;
+use super::async_websocket_client::{EmbeddedWebsocketError, EmbeddedWebsocketFramerError};
+use alloc::string::String;
 use core::fmt::Debug;
 use core::str::Utf8Error;
 use thiserror_no_std::Error;
@@ -22,6 +23,10 @@ pub enum WebsocketError<E: Debug> {
     Disconnected,
     #[error("Read buffer is too small (size: {0:?})")]
     RxBufferTooSmall(usize),
+    #[error("Websocket handshake rejected by server (HTTP status: {0:?})")]
+    HandshakeRejected(Option<u16>),
+    #[error("Websocket protocol error: {0:?}")]
+    Protocol(String),
 }
 
 impl<E: Debug> From<EmbeddedWebsocketFramerError<E>> for WebsocketError<E> {
@@ -31,6 +36,9 @@ impl<E: Debug> From<EmbeddedWebsocketFramerError<E>> for WebsocketError<E> {
             EmbeddedWebsocketFramerError::FrameTooLarge(e) => WebsocketError::FrameTooLarge(e),
             EmbeddedWebsocketFramerError::Utf8(e) => WebsocketError::Utf8(e),
             EmbeddedWebsocketFramerError::HttpHeader(_) => WebsocketError::HttpHeader,
+            EmbeddedWebsocketFramerError::WebSocket(
+                EmbeddedWebsocketError::HttpResponseCodeInvalid(status),
+            ) => WebsocketError::HandshakeRejected(status),
             EmbeddedWebsocketFramerError::WebSocket(e) => WebsocketError::WebSocket(e),
             EmbeddedWebsocketFramerError::Disconnected => WebsocketError::Disconnected,
             EmbeddedWebsocketFramerError::RxBufferTooSmall(e) => {
@@ -40,5 +48,25 @@ impl<E: Debug> From<EmbeddedWebsocketFramerError<E>> for WebsocketError<E> {
     }
 }
 
+#[cfg(feature = "std")]
+impl From<tokio_tungstenite::tungstenite::Error> for WebsocketError<alloc::io::Error> {
+    fn from(value: tokio_tungstenite::tungstenite::Error) -> Self {
+        use alloc::string::ToString;
+        use tokio_tungstenite::tungstenite::Error as TungsteniteError;
+
+        match value {
+            TungsteniteError::Io(e) => WebsocketError::Io(e),
+            TungsteniteError::Http(response) => {
+                WebsocketError::HandshakeRejected(Some(response.status().as_u16()))
+            }
+            TungsteniteError::HttpFormat(_) => WebsocketError::HttpHeader,
+            TungsteniteError::ConnectionClosed | TungsteniteError::AlreadyClosed => {
+                WebsocketError::Disconnected
+            }
+            other => WebsocketError::Protocol(other.to_string()),
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl<E: Debug> alloc::error::Error for WebsocketError<E> {}
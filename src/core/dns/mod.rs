@@ -1,6 +1,7 @@
 mod queries;
 
 use crate::core::dns::queries::{Aaaa, Lookup, A};
+use crate::core::io::timeout::{timeout, Duration};
 use alloc::borrow::Cow;
 use anyhow::Result;
 use core::marker::PhantomData;
@@ -18,6 +19,11 @@ pub async fn lookup(url: Cow<'_, str>) -> Result<IpAddr> {
     }
 }
 
+/// Like [`lookup`] but fails if no address could be resolved within `lookup_timeout`.
+pub async fn lookup_with_timeout(url: Cow<'_, str>, lookup_timeout: Duration) -> Result<IpAddr> {
+    timeout(lookup_timeout, lookup(url)).await?
+}
+
 pub struct Dns<'a, T = Aaaa> {
     url: Cow<'a, str>,
     record_type: PhantomData<T>,
@@ -36,10 +42,18 @@ impl<'a> Dns<'a, A> {
     pub async fn lookup(&self) -> Result<Ipv4Addr> {
         A::lookup(self.url.clone()).await
     }
+
+    pub async fn lookup_with_timeout(&self, lookup_timeout: Duration) -> Result<Ipv4Addr> {
+        timeout(lookup_timeout, self.lookup()).await?
+    }
 }
 
 impl<'a> Dns<'a, Aaaa> {
     pub async fn lookup(&self) -> Result<Ipv6Addr> {
         Aaaa::lookup(self.url.clone()).await
     }
+
+    pub async fn lookup_with_timeout(&self, lookup_timeout: Duration) -> Result<Ipv6Addr> {
+        timeout(lookup_timeout, self.lookup()).await?
+    }
 }
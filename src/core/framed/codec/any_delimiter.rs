@@ -0,0 +1,173 @@
+//! A no_std version of `tokio_util::codec::AnyDelimiterCodec`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio-util/src/codec/any_delimiter_codec.rs>`
+
+use crate::core::framed::codec::{Decoder, Encoder};
+use crate::core::framed::IoError;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use core::cmp::min;
+
+/// Splits chunks at any byte in `seek_delimiters`. Encoded chunks are terminated with
+/// `sequence_writer`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnyDelimiterCodec {
+    scanner: DelimiterScanner,
+    seek_delimiters: Vec<u8>,
+    sequence_writer: Vec<u8>,
+}
+
+impl AnyDelimiterCodec {
+    pub fn new(seek_delimiters: Vec<u8>, sequence_writer: Vec<u8>) -> Self {
+        Self::new_with_max_length(seek_delimiters, sequence_writer, usize::MAX)
+    }
+
+    /// Chunks longer than `max_length` fail to decode with [`IoError::FrameTooLong`] and are
+    /// skipped up to the next delimiter.
+    pub fn new_with_max_length(
+        seek_delimiters: Vec<u8>,
+        sequence_writer: Vec<u8>,
+        max_length: usize,
+    ) -> Self {
+        Self {
+            scanner: DelimiterScanner::new(max_length),
+            seek_delimiters,
+            sequence_writer,
+        }
+    }
+
+    pub fn max_length(&self) -> usize {
+        self.scanner.max_length
+    }
+}
+
+impl Decoder for AnyDelimiterCodec {
+    type Item = Bytes;
+    type Error = IoError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let chunk = self.scanner.decode(
+            buf,
+            |byte| self.seek_delimiters.contains(&byte),
+            IoError::FrameTooLong,
+        )?;
+
+        Ok(chunk.map(BytesMut::freeze))
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let chunk = self.scanner.decode_eof(
+            buf,
+            |byte| self.seek_delimiters.contains(&byte),
+            IoError::FrameTooLong,
+        )?;
+
+        Ok(chunk.map(BytesMut::freeze))
+    }
+
+    /// Too long chunks are skipped up to the next delimiter.
+    fn is_recoverable(&self, error: &Self::Error) -> bool {
+        matches!(error, IoError::FrameTooLong)
+    }
+}
+
+impl<T: AsRef<[u8]>> Encoder<T> for AnyDelimiterCodec {
+    type Error = IoError;
+
+    fn encode(&mut self, chunk: T, buf: &mut BytesMut) -> Result<(), Self::Error> {
+        let chunk = chunk.as_ref();
+        buf.reserve(chunk.len() + self.sequence_writer.len());
+        buf.put(chunk);
+        buf.put(&self.sequence_writer[..]);
+
+        Ok(())
+    }
+}
+
+/// Finds delimited chunks of at most `max_length` bytes, shared by the delimiter based codecs.
+/// Remembers how far it searched, so that bytes are only scanned once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct DelimiterScanner {
+    /// Where to continue searching for a delimiter.
+    next_index: usize,
+    pub(super) max_length: usize,
+    /// Set after a too long chunk, which is skipped up to the next delimiter.
+    is_discarding: bool,
+}
+
+impl DelimiterScanner {
+    pub(super) fn new(max_length: usize) -> Self {
+        Self {
+            next_index: 0,
+            max_length,
+            is_discarding: false,
+        }
+    }
+
+    /// Returns the next chunk without its delimiter.
+    pub(super) fn decode(
+        &mut self,
+        buf: &mut BytesMut,
+        is_delimiter: impl Fn(u8) -> bool,
+        too_long: IoError,
+    ) -> Result<Option<BytesMut>, IoError> {
+        loop {
+            // Searching one byte past `max_length` finds the delimiter of a chunk that is
+            // exactly `max_length` long.
+            let read_to = min(self.max_length.saturating_add(1), buf.len());
+            let offset = buf[self.next_index..read_to]
+                .iter()
+                .position(|byte| is_delimiter(*byte));
+
+            match (self.is_discarding, offset) {
+                (true, Some(offset)) => {
+                    buf.advance(self.next_index + offset + 1);
+                    self.is_discarding = false;
+                    self.next_index = 0;
+                }
+                (true, None) => {
+                    buf.advance(read_to);
+                    self.next_index = 0;
+                    if buf.is_empty() {
+                        return Ok(None);
+                    }
+                }
+                (false, Some(offset)) => {
+                    let delimiter_index = self.next_index + offset;
+                    self.next_index = 0;
+                    let mut chunk = buf.split_to(delimiter_index + 1);
+                    chunk.truncate(delimiter_index);
+                    return Ok(Some(chunk));
+                }
+                (false, None) if buf.len() > self.max_length => {
+                    self.is_discarding = true;
+                    return Err(too_long);
+                }
+                (false, None) => {
+                    self.next_index = read_to;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    /// Like [`DelimiterScanner::decode`], but returns the remaining bytes as the last chunk.
+    pub(super) fn decode_eof(
+        &mut self,
+        buf: &mut BytesMut,
+        is_delimiter: impl Fn(u8) -> bool,
+        too_long: IoError,
+    ) -> Result<Option<BytesMut>, IoError> {
+        if let Some(chunk) = self.decode(buf, is_delimiter, too_long)? {
+            return Ok(Some(chunk));
+        }
+
+        self.next_index = 0;
+        if buf.is_empty() {
+            return Ok(None);
+        }
+        let len = buf.len();
+
+        Ok(Some(buf.split_to(len)))
+    }
+}
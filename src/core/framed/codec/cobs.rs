@@ -0,0 +1,129 @@
+//! COBS framing (Consistent Overhead Byte Stuffing)
+//!
+//! The payload is encoded without `0x00` bytes, at most one byte of overhead per 254 bytes,
+//! and each frame ends with `0x00`.
+
+use crate::core::framed::codec::any_delimiter::DelimiterScanner;
+use crate::core::framed::codec::{Decoder, Encoder};
+use crate::core::framed::IoError;
+use bytes::{BufMut, BytesMut};
+
+const DELIMITER: u8 = 0x00;
+/// The code of a block with 254 bytes and no zero after them.
+const MAX_CODE: u8 = 0xff;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CobsCodec {
+    scanner: DelimiterScanner,
+    max_frame_len: usize,
+}
+
+impl Default for CobsCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CobsCodec {
+    /// A codec without a maximum frame length.
+    pub fn new() -> Self {
+        Self::new_with_max_length(usize::MAX)
+    }
+
+    /// Frames decoding to more than `max_frame_len` bytes fail with [`IoError::FrameTooLong`]
+    /// and are skipped.
+    pub fn new_with_max_length(max_frame_len: usize) -> Self {
+        Self {
+            scanner: DelimiterScanner::new(max_encoded_len(max_frame_len)),
+            max_frame_len,
+        }
+    }
+
+    pub fn max_frame_length(&self) -> usize {
+        self.max_frame_len
+    }
+
+    fn decode_frame(&self, frame: &[u8]) -> Result<BytesMut, IoError> {
+        let mut output = BytesMut::with_capacity(frame.len());
+        let mut index = 0;
+        while index < frame.len() {
+            let code = frame[index];
+            let end = index + code as usize;
+            // Zeros are delimiters, so `code` is at least 1.
+            if end > frame.len() {
+                return Err(IoError::InvalidFrame);
+            }
+            output.put_slice(&frame[index + 1..end]);
+            index = end;
+            if code != MAX_CODE && index < frame.len() {
+                output.put_u8(0);
+            }
+        }
+        if output.len() > self.max_frame_len {
+            return Err(IoError::FrameTooLong);
+        }
+
+        Ok(output)
+    }
+}
+
+fn max_encoded_len(len: usize) -> usize {
+    len.saturating_add(len / 254).saturating_add(1)
+}
+
+impl Decoder for CobsCodec {
+    type Item = BytesMut;
+    type Error = IoError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            match self
+                .scanner
+                .decode(buf, |byte| byte == DELIMITER, IoError::FrameTooLong)?
+            {
+                // Repeated delimiters, e.g. sent to resynchronise the receiver.
+                Some(frame) if frame.is_empty() => continue,
+                Some(frame) => return self.decode_frame(&frame).map(Some),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Invalid and too long frames are skipped up to the next delimiter.
+    fn is_recoverable(&self, error: &Self::Error) -> bool {
+        matches!(error, IoError::InvalidFrame | IoError::FrameTooLong)
+    }
+}
+
+impl<T: AsRef<[u8]>> Encoder<T> for CobsCodec {
+    type Error = IoError;
+
+    fn encode(&mut self, data: T, buf: &mut BytesMut) -> Result<(), Self::Error> {
+        let data = data.as_ref();
+        if data.len() > self.max_frame_len {
+            return Err(IoError::FrameTooLong);
+        }
+
+        buf.reserve(max_encoded_len(data.len()) + 1);
+        let mut code_index = buf.len();
+        let mut code = 1;
+        buf.put_u8(code);
+        for (index, byte) in data.iter().enumerate() {
+            if *byte != 0 {
+                buf.put_u8(*byte);
+                code += 1;
+            }
+            // A zero ends the block, and so do 254 bytes unless they end the frame.
+            if *byte == 0 || (code == MAX_CODE && index + 1 < data.len()) {
+                buf[code_index] = code;
+                code_index = buf.len();
+                code = 1;
+                buf.put_u8(code);
+            }
+        }
+        buf[code_index] = code;
+        buf.put_u8(DELIMITER);
+
+        Ok(())
+    }
+}
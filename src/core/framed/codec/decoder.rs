@@ -22,6 +22,13 @@ pub trait Decoder {
         }
     }
 
+    /// Returns `true` if decoding can go on after `error`, because the codec skipped the
+    /// corrupt bytes. `Framed` then yields the error and keeps reading instead of ending the
+    /// stream.
+    fn is_recoverable(&self, _error: &Self::Error) -> bool {
+        false
+    }
+
     fn framed<T: AsyncRead + AsyncWrite + Sized>(self, io: T) -> Framed<T, Self>
     where
         Self: Sized,
@@ -0,0 +1,195 @@
+//! HDLC-like framing (RFC 1662) with a CRC trailer
+//!
+//! Frames are enclosed in `FLAG` bytes, `FLAG` and `ESCAPE` bytes in the frame are escaped,
+//! and the payload is followed by a CRC-16-CCITT (X.25) or CRC-32 checksum, least significant
+//! byte first. Frames with a wrong checksum fail with [`IoError::ChecksumMismatch`] and are
+//! skipped, so `Framed` resynchronises on the next flag.
+
+use crate::core::framed::codec::any_delimiter::DelimiterScanner;
+use crate::core::framed::codec::{Decoder, Encoder};
+use crate::core::framed::IoError;
+use bytes::{BufMut, BytesMut};
+
+const FLAG: u8 = 0x7e;
+const ESCAPE: u8 = 0x7d;
+const ESCAPE_XOR: u8 = 0x20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Checksum {
+    /// CRC-16-CCITT as used by X.25 and PPP (FCS-16).
+    Crc16,
+    /// CRC-32 as used by Ethernet and PPP (FCS-32).
+    Crc32,
+}
+
+impl Checksum {
+    /// Returns the size of the trailer in bytes.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Crc16 => 2,
+            Self::Crc32 => 4,
+        }
+    }
+
+    fn compute(&self, data: &[u8]) -> u32 {
+        match self {
+            Self::Crc16 => crc16(data) as u32,
+            Self::Crc32 => crc32(data),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HdlcCodec {
+    scanner: DelimiterScanner,
+    checksum: Checksum,
+    max_frame_len: usize,
+}
+
+impl HdlcCodec {
+    /// A codec without a maximum frame length.
+    pub fn new(checksum: Checksum) -> Self {
+        Self::new_with_max_length(checksum, usize::MAX)
+    }
+
+    /// Payloads longer than `max_frame_len` bytes fail with [`IoError::FrameTooLong`] and are
+    /// skipped.
+    pub fn new_with_max_length(checksum: Checksum, max_frame_len: usize) -> Self {
+        Self {
+            // Every byte of the payload and the checksum may be escaped.
+            scanner: DelimiterScanner::new(
+                max_frame_len
+                    .saturating_add(checksum.size())
+                    .saturating_mul(2),
+            ),
+            checksum,
+            max_frame_len,
+        }
+    }
+
+    pub fn checksum(&self) -> Checksum {
+        self.checksum
+    }
+
+    pub fn max_frame_length(&self) -> usize {
+        self.max_frame_len
+    }
+
+    fn decode_frame(&self, frame: &[u8]) -> Result<BytesMut, IoError> {
+        let mut output = BytesMut::with_capacity(frame.len());
+        let mut bytes = frame.iter();
+        while let Some(byte) = bytes.next() {
+            let byte = match *byte {
+                ESCAPE => match bytes.next() {
+                    Some(byte) => byte ^ ESCAPE_XOR,
+                    None => return Err(IoError::InvalidFrame),
+                },
+                byte => byte,
+            };
+            output.put_u8(byte);
+        }
+
+        let checksum_len = self.checksum.size();
+        if output.len() < checksum_len {
+            return Err(IoError::InvalidFrame);
+        }
+        let payload_len = output.len() - checksum_len;
+        if payload_len > self.max_frame_len {
+            return Err(IoError::FrameTooLong);
+        }
+        let received = output[payload_len..]
+            .iter()
+            .rev()
+            .fold(0u32, |checksum, byte| checksum << 8 | *byte as u32);
+        if received != self.checksum.compute(&output[..payload_len]) {
+            return Err(IoError::ChecksumMismatch);
+        }
+        output.truncate(payload_len);
+
+        Ok(output)
+    }
+}
+
+impl Decoder for HdlcCodec {
+    type Item = BytesMut;
+    type Error = IoError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            match self
+                .scanner
+                .decode(buf, |byte| byte == FLAG, IoError::FrameTooLong)?
+            {
+                // The closing flag of one frame may double as the opening flag of the next.
+                Some(frame) if frame.is_empty() => continue,
+                Some(frame) => return self.decode_frame(&frame).map(Some),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Corrupt frames are skipped up to the next flag.
+    fn is_recoverable(&self, error: &Self::Error) -> bool {
+        matches!(
+            error,
+            IoError::ChecksumMismatch | IoError::InvalidFrame | IoError::FrameTooLong
+        )
+    }
+}
+
+impl<T: AsRef<[u8]>> Encoder<T> for HdlcCodec {
+    type Error = IoError;
+
+    fn encode(&mut self, data: T, buf: &mut BytesMut) -> Result<(), Self::Error> {
+        let data = data.as_ref();
+        if data.len() > self.max_frame_len {
+            return Err(IoError::FrameTooLong);
+        }
+
+        let checksum = self.checksum.compute(data).to_le_bytes();
+        buf.reserve(data.len() + self.checksum.size() + 2);
+        buf.put_u8(FLAG);
+        for byte in data.iter().chain(&checksum[..self.checksum.size()]) {
+            match *byte {
+                FLAG | ESCAPE => buf.put_slice(&[ESCAPE, byte ^ ESCAPE_XOR]),
+                byte => buf.put_u8(byte),
+            }
+        }
+        buf.put_u8(FLAG);
+
+        Ok(())
+    }
+}
+
+/// CRC-16/X-25: reflected polynomial `0x1021`, initial value and final XOR `0xffff`.
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0xffffu16;
+    for byte in data {
+        crc ^= *byte as u16;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0x8408,
+                _ => crc >> 1,
+            };
+        }
+    }
+
+    !crc
+}
+
+/// CRC-32/ISO-HDLC: reflected polynomial `0x04c11db7`, initial value and final XOR
+/// `0xffffffff`.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0xedb8_8320,
+                _ => crc >> 1,
+            };
+        }
+    }
+
+    !crc
+}
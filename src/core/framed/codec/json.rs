@@ -0,0 +1,219 @@
+//! Typed JSON messages over a framing layer.
+//!
+//! Messages are either separated by newlines (JSON lines, compact JSON never contains a raw
+//! newline) or prefixed with their length by a [`LengthDelimitedCodec`]. [`JsonCodec`] uses
+//! `serde_json`, [`HeaplessJsonCodec`] uses `serde-json-core` and serializes into a fixed
+//! buffer of `N` bytes.
+
+use crate::core::framed::codec::any_delimiter::DelimiterScanner;
+use crate::core::framed::codec::{Decoder, Encoder, LengthDelimitedCodec};
+use crate::core::framed::IoError;
+use bytes::{BufMut, BytesMut};
+use core::marker::PhantomData;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Framing {
+    Newline(DelimiterScanner),
+    LengthDelimited(LengthDelimitedCodec),
+}
+
+impl Framing {
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<BytesMut>, IoError> {
+        match self {
+            Self::Newline(scanner) => scanner.decode(buf, is_newline, IoError::FrameTooLong),
+            Self::LengthDelimited(codec) => codec.decode(buf),
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<BytesMut>, IoError> {
+        match self {
+            Self::Newline(scanner) => scanner.decode_eof(buf, is_newline, IoError::FrameTooLong),
+            Self::LengthDelimited(codec) => codec.decode_eof(buf),
+        }
+    }
+
+    fn encode(&mut self, message: &[u8], buf: &mut BytesMut) -> Result<(), IoError> {
+        match self {
+            Self::Newline(_) => {
+                buf.reserve(message.len() + 1);
+                buf.put(message);
+                buf.put_u8(b'\n');
+                Ok(())
+            }
+            Self::LengthDelimited(codec) => codec.encode(message, buf),
+        }
+    }
+
+    /// Invalid JSON can be skipped, and so can too long lines. A too long length delimited
+    /// frame can't, its end is unknown.
+    fn is_recoverable(&self, error: &IoError) -> bool {
+        match self {
+            Self::Newline(_) => matches!(error, IoError::InvalidJson | IoError::FrameTooLong),
+            Self::LengthDelimited(_) => matches!(error, IoError::InvalidJson),
+        }
+    }
+}
+
+fn is_newline(byte: u8) -> bool {
+    byte == b'\n'
+}
+
+/// Decodes frames into `T` and encodes `T` into frames, using `serde_json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonCodec<T> {
+    framing: Framing,
+    _message: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for JsonCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> JsonCodec<T> {
+    /// One message per line, without a maximum length.
+    pub fn new() -> Self {
+        Self::new_with_max_length(usize::MAX)
+    }
+
+    /// One message per line. Longer lines than `max_length` fail with
+    /// [`IoError::FrameTooLong`] and are skipped.
+    pub fn new_with_max_length(max_length: usize) -> Self {
+        Self {
+            framing: Framing::Newline(DelimiterScanner::new(max_length)),
+            _message: PhantomData,
+        }
+    }
+
+    /// Messages framed by `codec`.
+    pub fn length_delimited(codec: LengthDelimitedCodec) -> Self {
+        Self {
+            framing: Framing::LengthDelimited(codec),
+            _message: PhantomData,
+        }
+    }
+
+    fn deserialize(frame: Option<BytesMut>) -> Result<Option<T>, IoError>
+    where
+        T: DeserializeOwned,
+    {
+        match frame {
+            Some(frame) => match serde_json::from_slice(&frame) {
+                Ok(message) => Ok(Some(message)),
+                Err(_) => Err(IoError::InvalidJson),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Decoder for JsonCodec<T> {
+    type Item = T;
+    type Error = IoError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        Self::deserialize(self.framing.decode(buf)?)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        Self::deserialize(self.framing.decode_eof(buf)?)
+    }
+
+    fn is_recoverable(&self, error: &Self::Error) -> bool {
+        self.framing.is_recoverable(error)
+    }
+}
+
+impl<T: Serialize> Encoder<T> for JsonCodec<T> {
+    type Error = IoError;
+
+    fn encode(&mut self, message: T, buf: &mut BytesMut) -> Result<(), Self::Error> {
+        match serde_json::to_vec(&message) {
+            Ok(message) => self.framing.encode(&message, buf),
+            Err(_) => Err(IoError::UnableToSerialize),
+        }
+    }
+}
+
+/// Like [`JsonCodec`], but uses `serde-json-core` and serializes into a buffer of `N` bytes,
+/// so that neither needs the heap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaplessJsonCodec<T, const N: usize> {
+    framing: Framing,
+    buffer: [u8; N],
+    _message: PhantomData<fn() -> T>,
+}
+
+impl<T, const N: usize> Default for HeaplessJsonCodec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> HeaplessJsonCodec<T, N> {
+    /// One message of at most `N` bytes per line.
+    pub fn new() -> Self {
+        Self {
+            framing: Framing::Newline(DelimiterScanner::new(N)),
+            buffer: [0; N],
+            _message: PhantomData,
+        }
+    }
+
+    /// Messages framed by `codec`, whose maximum frame length is limited to `N`.
+    pub fn length_delimited(mut codec: LengthDelimitedCodec) -> Self {
+        if codec.max_frame_length() > N {
+            codec.set_max_frame_length(N);
+        }
+        Self {
+            framing: Framing::LengthDelimited(codec),
+            buffer: [0; N],
+            _message: PhantomData,
+        }
+    }
+
+    fn deserialize(frame: Option<BytesMut>) -> Result<Option<T>, IoError>
+    where
+        T: DeserializeOwned,
+    {
+        match frame {
+            Some(frame) => match serde_json_core::from_slice(&frame) {
+                Ok((message, _)) => Ok(Some(message)),
+                Err(_) => Err(IoError::InvalidJson),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T: DeserializeOwned, const N: usize> Decoder for HeaplessJsonCodec<T, N> {
+    type Item = T;
+    type Error = IoError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        Self::deserialize(self.framing.decode(buf)?)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        Self::deserialize(self.framing.decode_eof(buf)?)
+    }
+
+    fn is_recoverable(&self, error: &Self::Error) -> bool {
+        self.framing.is_recoverable(error)
+    }
+}
+
+impl<T: Serialize, const N: usize> Encoder<T> for HeaplessJsonCodec<T, N> {
+    type Error = IoError;
+
+    fn encode(&mut self, message: T, buf: &mut BytesMut) -> Result<(), Self::Error> {
+        match serde_json_core::to_slice(&message, &mut self.buffer) {
+            Ok(len) => self.framing.encode(&self.buffer[..len], buf),
+            Err(serde_json_core::ser::Error::BufferFull) => Err(IoError::FrameTooLong),
+            Err(_) => Err(IoError::UnableToSerialize),
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! A no_std version of `tokio_util::codec::LengthDelimitedCodec`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio-util/src/codec/length_delimited.rs>`
+//!
+//! Frames are prefixed with their length. The header may have bytes before the length field
+//! (`length_field_offset`), the length may count more or less than the payload
+//! (`length_adjustment`) and decoding can keep part of the header in the frame (`num_skip`).
+//! Encoding only writes the length field, followed by the payload.
+
+use crate::core::framed::codec::{Decoder, Encoder};
+use crate::core::framed::{Framed, IoError};
+use crate::core::io::{AsyncRead, AsyncWrite};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Builder {
+    max_frame_len: usize,
+    length_field_len: usize,
+    length_field_offset: usize,
+    length_adjustment: isize,
+    num_skip: Option<usize>,
+    big_endian: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DecodeState {
+    Head,
+    Data(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthDelimitedCodec {
+    builder: Builder,
+    state: DecodeState,
+}
+
+impl Default for LengthDelimitedCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LengthDelimitedCodec {
+    /// A codec with a 4 byte big endian length field and a maximum frame length of 8 MB.
+    pub fn new() -> Self {
+        Builder::new().new_codec()
+    }
+
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    pub fn max_frame_length(&self) -> usize {
+        self.builder.max_frame_len
+    }
+
+    pub fn set_max_frame_length(&mut self, max_frame_len: usize) {
+        self.builder.max_frame_length(max_frame_len);
+    }
+
+    fn decode_head(&mut self, src: &mut BytesMut) -> Result<Option<usize>, IoError> {
+        let head_len = self.builder.num_head_bytes();
+        let field_len = self.builder.length_field_len;
+        if src.len() < head_len {
+            return Ok(None);
+        }
+
+        let n = {
+            let mut field = &src[self.builder.length_field_offset..head_len];
+            let n = match self.builder.big_endian {
+                true => field.get_uint(field_len),
+                false => field.get_uint_le(field_len),
+            };
+            if n > self.builder.max_frame_len as u64 {
+                return Err(IoError::FrameTooLong);
+            }
+            let n = n as usize;
+            let adjusted = match self.builder.length_adjustment < 0 {
+                true => n.checked_sub(self.builder.length_adjustment.unsigned_abs()),
+                false => n.checked_add(self.builder.length_adjustment as usize),
+            };
+            match adjusted {
+                Some(n) => n,
+                None => return Err(IoError::InvalidFrameLength),
+            }
+        };
+
+        src.advance(self.builder.get_num_skip());
+        // Make room for the whole frame right away.
+        src.reserve(n.saturating_sub(src.len()));
+
+        Ok(Some(n))
+    }
+
+    fn decode_data(&self, n: usize, src: &mut BytesMut) -> Option<BytesMut> {
+        if src.len() < n {
+            return None;
+        }
+
+        Some(src.split_to(n))
+    }
+
+    fn encode_slice(&mut self, data: &[u8], dst: &mut BytesMut) -> Result<(), IoError> {
+        let n = data.len();
+        if n > self.builder.max_frame_len {
+            return Err(IoError::FrameTooLong);
+        }
+
+        // The adjustment is applied in reverse, so that decoding reverts it.
+        let n = match self.builder.length_adjustment < 0 {
+            true => n.checked_add(self.builder.length_adjustment.unsigned_abs()),
+            false => n.checked_sub(self.builder.length_adjustment as usize),
+        };
+        let n = match n {
+            Some(n) => n as u64,
+            None => return Err(IoError::InvalidFrameLength),
+        };
+        let field_len = self.builder.length_field_len;
+        if field_len < 8 && n >> (field_len * 8) != 0 {
+            return Err(IoError::FrameTooLong);
+        }
+
+        dst.reserve(field_len + data.len());
+        match self.builder.big_endian {
+            true => dst.put_uint(n, field_len),
+            false => dst.put_uint_le(n, field_len),
+        }
+        dst.extend_from_slice(data);
+
+        Ok(())
+    }
+}
+
+impl Decoder for LengthDelimitedCodec {
+    type Item = BytesMut;
+    type Error = IoError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let n = match self.state {
+            DecodeState::Head => match self.decode_head(src)? {
+                Some(n) => {
+                    self.state = DecodeState::Data(n);
+                    n
+                }
+                None => return Ok(None),
+            },
+            DecodeState::Data(n) => n,
+        };
+
+        match self.decode_data(n, src) {
+            Some(data) => {
+                self.state = DecodeState::Head;
+                src.reserve(self.builder.num_head_bytes().saturating_sub(src.len()));
+                Ok(Some(data))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl Encoder<&[u8]> for LengthDelimitedCodec {
+    type Error = IoError;
+
+    fn encode(&mut self, data: &[u8], dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode_slice(data, dst)
+    }
+}
+
+impl Encoder<Bytes> for LengthDelimitedCodec {
+    type Error = IoError;
+
+    fn encode(&mut self, data: Bytes, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode_slice(&data, dst)
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self {
+            max_frame_len: 8 * 1024 * 1024,
+            length_field_len: 4,
+            length_field_offset: 0,
+            length_adjustment: 0,
+            num_skip: None,
+            big_endian: true,
+        }
+    }
+
+    pub fn big_endian(&mut self) -> &mut Self {
+        self.big_endian = true;
+        self
+    }
+
+    pub fn little_endian(&mut self) -> &mut Self {
+        self.big_endian = false;
+        self
+    }
+
+    pub fn native_endian(&mut self) -> &mut Self {
+        self.big_endian = cfg!(target_endian = "big");
+        self
+    }
+
+    /// Frames longer than this are rejected while decoding and encoding.
+    pub fn max_frame_length(&mut self, max_frame_len: usize) -> &mut Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// The size of the length field in bytes.
+    ///
+    /// # Panics
+    ///
+    /// If `length_field_len` is not between 1 and 8.
+    pub fn length_field_length(&mut self, length_field_len: usize) -> &mut Self {
+        assert!(
+            (1..=8).contains(&length_field_len),
+            "the length field must be 1 to 8 bytes long"
+        );
+        self.length_field_len = length_field_len;
+        self
+    }
+
+    /// The number of header bytes before the length field.
+    pub fn length_field_offset(&mut self, length_field_offset: usize) -> &mut Self {
+        self.length_field_offset = length_field_offset;
+        self
+    }
+
+    /// Added to the length field to get the payload length, e.g. `-2` if the length counts a
+    /// 2 byte length field as well.
+    pub fn length_adjustment(&mut self, length_adjustment: isize) -> &mut Self {
+        self.length_adjustment = length_adjustment;
+        self
+    }
+
+    /// The number of bytes cut off the start of each decoded frame. Defaults to the header
+    /// length (`length_field_offset + length_field_length`).
+    pub fn num_skip(&mut self, num_skip: usize) -> &mut Self {
+        self.num_skip = Some(num_skip);
+        self
+    }
+
+    pub fn new_codec(&self) -> LengthDelimitedCodec {
+        LengthDelimitedCodec {
+            builder: *self,
+            state: DecodeState::Head,
+        }
+    }
+
+    pub fn new_framed<T: AsyncRead + AsyncWrite>(
+        &self,
+        inner: T,
+    ) -> Framed<T, LengthDelimitedCodec> {
+        Framed::new(inner, self.new_codec())
+    }
+
+    fn num_head_bytes(&self) -> usize {
+        self.length_field_offset + self.length_field_len
+    }
+
+    fn get_num_skip(&self) -> usize {
+        self.num_skip.unwrap_or_else(|| self.num_head_bytes())
+    }
+}
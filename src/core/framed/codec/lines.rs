@@ -0,0 +1,187 @@
+//! A no_std version of `tokio_util::codec::LinesCodec`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio-util/src/codec/lines_codec.rs>`
+//!
+//! Lines end with `\n` or `\r\n`, the line ending is not part of the decoded line.
+//! [`HeaplessLinesCodec`] decodes into a `heapless::String` instead of a `String`.
+
+use crate::core::framed::codec::any_delimiter::DelimiterScanner;
+use crate::core::framed::codec::{Decoder, Encoder};
+use crate::core::framed::IoError;
+use alloc::string::String;
+use bytes::{BufMut, BytesMut};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LinesCodec {
+    scanner: DelimiterScanner,
+    crlf: bool,
+}
+
+impl Default for LinesCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinesCodec {
+    /// A codec without a maximum line length. Prefer [`LinesCodec::new_with_max_length`] for
+    /// untrusted peers, which could otherwise make the read buffer grow without bounds.
+    pub fn new() -> Self {
+        Self::new_with_max_length(usize::MAX)
+    }
+
+    /// Lines longer than `max_length` fail to decode with [`IoError::LineTooLong`] and are
+    /// skipped up to the next line ending.
+    pub fn new_with_max_length(max_length: usize) -> Self {
+        Self {
+            scanner: DelimiterScanner::new(max_length),
+            crlf: false,
+        }
+    }
+
+    /// Terminates encoded lines with `\r\n` instead of `\n`, e.g. for AT commands.
+    pub fn with_crlf(mut self) -> Self {
+        self.crlf = true;
+        self
+    }
+
+    pub fn max_length(&self) -> usize {
+        self.scanner.max_length
+    }
+}
+
+impl Decoder for LinesCodec {
+    type Item = String;
+    type Error = IoError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.scanner.decode(buf, is_newline, IoError::LineTooLong)? {
+            Some(line) => Ok(Some(String::from(to_str(&line)?))),
+            None => Ok(None),
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self
+            .scanner
+            .decode_eof(buf, is_newline, IoError::LineTooLong)?
+        {
+            Some(line) if line[..] == b"\r"[..] => Ok(None),
+            Some(line) => Ok(Some(String::from(to_str(&line)?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Too long lines and invalid UTF-8 are skipped up to the next line ending.
+    fn is_recoverable(&self, error: &Self::Error) -> bool {
+        matches!(error, IoError::LineTooLong | IoError::InvalidUtf8)
+    }
+}
+
+impl<T: AsRef<str>> Encoder<T> for LinesCodec {
+    type Error = IoError;
+
+    fn encode(&mut self, line: T, buf: &mut BytesMut) -> Result<(), Self::Error> {
+        encode_line(line.as_ref(), self.crlf, buf);
+        Ok(())
+    }
+}
+
+/// A [`LinesCodec`] decoding into a `heapless::String<N>`, so lines are at most `N` bytes long.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HeaplessLinesCodec<const N: usize> {
+    scanner: DelimiterScanner,
+    crlf: bool,
+}
+
+impl<const N: usize> Default for HeaplessLinesCodec<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> HeaplessLinesCodec<N> {
+    pub fn new() -> Self {
+        Self {
+            // Leaves room for a `\r` before the `\n`.
+            scanner: DelimiterScanner::new(N + 1),
+            crlf: false,
+        }
+    }
+
+    /// Terminates encoded lines with `\r\n` instead of `\n`, e.g. for AT commands.
+    pub fn with_crlf(mut self) -> Self {
+        self.crlf = true;
+        self
+    }
+
+    fn to_line(line: &[u8]) -> Result<heapless::String<N>, IoError> {
+        let mut output = heapless::String::new();
+        match output.push_str(to_str(line)?) {
+            Ok(()) => Ok(output),
+            Err(()) => Err(IoError::LineTooLong),
+        }
+    }
+}
+
+impl<const N: usize> Decoder for HeaplessLinesCodec<N> {
+    type Item = heapless::String<N>;
+    type Error = IoError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.scanner.decode(buf, is_newline, IoError::LineTooLong)? {
+            Some(line) => Self::to_line(&line).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self
+            .scanner
+            .decode_eof(buf, is_newline, IoError::LineTooLong)?
+        {
+            Some(line) if line[..] == b"\r"[..] => Ok(None),
+            Some(line) => Self::to_line(&line).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Too long lines and invalid UTF-8 are skipped up to the next line ending.
+    fn is_recoverable(&self, error: &Self::Error) -> bool {
+        matches!(error, IoError::LineTooLong | IoError::InvalidUtf8)
+    }
+}
+
+impl<T: AsRef<str>, const N: usize> Encoder<T> for HeaplessLinesCodec<N> {
+    type Error = IoError;
+
+    fn encode(&mut self, line: T, buf: &mut BytesMut) -> Result<(), Self::Error> {
+        encode_line(line.as_ref(), self.crlf, buf);
+        Ok(())
+    }
+}
+
+fn is_newline(byte: u8) -> bool {
+    byte == b'\n'
+}
+
+/// Drops the `\r` of a `\r\n` line ending and checks the line is valid UTF-8.
+fn to_str(line: &[u8]) -> Result<&str, IoError> {
+    let line = match line.last() {
+        Some(b'\r') => &line[..line.len() - 1],
+        _ => line,
+    };
+    match core::str::from_utf8(line) {
+        Ok(line) => Ok(line),
+        Err(_) => Err(IoError::InvalidUtf8),
+    }
+}
+
+fn encode_line(line: &str, crlf: bool, buf: &mut BytesMut) {
+    buf.reserve(line.len() + 2);
+    buf.put(line.as_bytes());
+    if crlf {
+        buf.put_u8(b'\r');
+    }
+    buf.put_u8(b'\n');
+}
@@ -1,10 +1,26 @@
 use bytes::{BufMut, BytesMut};
 
+mod any_delimiter;
+mod cobs;
 mod decoder;
 mod encoder;
-
+pub mod hdlc;
+#[cfg(feature = "json")]
+mod json;
+pub mod length_delimited;
+mod lines;
+mod slip;
+
+pub use any_delimiter::AnyDelimiterCodec;
+pub use cobs::CobsCodec;
 pub use decoder::Decoder;
 pub use encoder::Encoder;
+pub use hdlc::{Checksum, HdlcCodec};
+#[cfg(feature = "json")]
+pub use json::{HeaplessJsonCodec, JsonCodec};
+pub use length_delimited::LengthDelimitedCodec;
+pub use lines::{HeaplessLinesCodec, LinesCodec};
+pub use slip::SlipCodec;
 
 use crate::core::framed::IoError;
 
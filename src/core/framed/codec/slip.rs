@@ -0,0 +1,116 @@
+//! SLIP framing (RFC 1055)
+//!
+//! Frames end with `END`, and `END` or `ESC` bytes in the payload are escaped. Frames are also
+//! sent with a leading `END`, which flushes line noise received before the frame. Empty frames
+//! are skipped, as the RFC suggests, so they can't be sent.
+
+use crate::core::framed::codec::any_delimiter::DelimiterScanner;
+use crate::core::framed::codec::{Decoder, Encoder};
+use crate::core::framed::IoError;
+use bytes::{BufMut, BytesMut};
+
+const END: u8 = 0xc0;
+const ESC: u8 = 0xdb;
+const ESC_END: u8 = 0xdc;
+const ESC_ESC: u8 = 0xdd;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SlipCodec {
+    scanner: DelimiterScanner,
+    max_frame_len: usize,
+}
+
+impl Default for SlipCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SlipCodec {
+    /// A codec without a maximum frame length.
+    pub fn new() -> Self {
+        Self::new_with_max_length(usize::MAX)
+    }
+
+    /// Frames decoding to more than `max_frame_len` bytes fail with [`IoError::FrameTooLong`]
+    /// and are skipped.
+    pub fn new_with_max_length(max_frame_len: usize) -> Self {
+        Self {
+            // Every payload byte may be escaped.
+            scanner: DelimiterScanner::new(max_frame_len.saturating_mul(2)),
+            max_frame_len,
+        }
+    }
+
+    pub fn max_frame_length(&self) -> usize {
+        self.max_frame_len
+    }
+
+    fn unescape(&self, frame: &[u8]) -> Result<BytesMut, IoError> {
+        let mut output = BytesMut::with_capacity(frame.len());
+        let mut bytes = frame.iter();
+        while let Some(byte) = bytes.next() {
+            let byte = match *byte {
+                ESC => match bytes.next() {
+                    Some(&ESC_END) => END,
+                    Some(&ESC_ESC) => ESC,
+                    _ => return Err(IoError::InvalidFrame),
+                },
+                byte => byte,
+            };
+            output.put_u8(byte);
+        }
+        if output.len() > self.max_frame_len {
+            return Err(IoError::FrameTooLong);
+        }
+
+        Ok(output)
+    }
+}
+
+impl Decoder for SlipCodec {
+    type Item = BytesMut;
+    type Error = IoError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            match self
+                .scanner
+                .decode(buf, |byte| byte == END, IoError::FrameTooLong)?
+            {
+                Some(frame) if frame.is_empty() => continue,
+                Some(frame) => return self.unescape(&frame).map(Some),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Invalid escapes and too long frames are skipped up to the next `END`.
+    fn is_recoverable(&self, error: &Self::Error) -> bool {
+        matches!(error, IoError::InvalidFrame | IoError::FrameTooLong)
+    }
+}
+
+impl<T: AsRef<[u8]>> Encoder<T> for SlipCodec {
+    type Error = IoError;
+
+    fn encode(&mut self, data: T, buf: &mut BytesMut) -> Result<(), Self::Error> {
+        let data = data.as_ref();
+        if data.len() > self.max_frame_len {
+            return Err(IoError::FrameTooLong);
+        }
+
+        buf.reserve(data.len() + 2);
+        buf.put_u8(END);
+        for byte in data {
+            match *byte {
+                END => buf.put_slice(&[ESC, ESC_END]),
+                ESC => buf.put_slice(&[ESC, ESC_ESC]),
+                byte => buf.put_u8(byte),
+            }
+        }
+        buf.put_u8(END);
+
+        Ok(())
+    }
+}
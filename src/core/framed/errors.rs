@@ -1,3 +1,4 @@
+use alloc::string::String;
 use thiserror_no_std::Error;
 
 #[derive(Debug, Clone, Error)]
@@ -42,6 +43,10 @@ pub enum IoError {
     // AsyncRead errors
     #[error("Error occured while reading from stream")]
     UnableToRead,
+    #[error("Stream ended before the buffer was filled")]
+    UnexpectedEof,
+    #[error("Read bytes are not valid UTF-8")]
+    InvalidUtf8,
 
     // AsyncWrite errors
     #[error("Error occured while writing to stream")]
@@ -50,10 +55,54 @@ pub enum IoError {
     UnableToFlush,
     #[error("Error occured while closing stream")]
     UnableToClose,
+    #[error("Stream accepted zero bytes while writing")]
+    WriteZero,
+    #[error("Tried to write but the other end of the stream is closed")]
+    BrokenPipe,
+    #[error("Tried to write past the write limit")]
+    WriteLimitReached,
+
+    // Timeout errors
+    #[error("Timed out while connecting")]
+    ConnectTimedOut,
+    #[error("Timed out while reading from stream")]
+    ReadTimedOut,
+    #[error("Timed out while writing to stream")]
+    WriteTimedOut,
+    #[error("Stream was idle for too long")]
+    IdleTimedOut,
+    #[error("Operation timed out")]
+    TimedOut,
+
+    // Codec errors
+    #[error("Frame is longer than the maximum frame length")]
+    FrameTooLong,
+    #[error("Frame length is out of range after the length adjustment")]
+    InvalidFrameLength,
+    #[error("Line is longer than the maximum line length")]
+    LineTooLong,
+    #[error("Frame is not correctly encoded")]
+    InvalidFrame,
+    #[error("Frame checksum does not match its content")]
+    ChecksumMismatch,
+    #[error("Frame is not valid JSON for the expected type")]
+    InvalidJson,
+    #[error("Unable to serialize the item to JSON")]
+    UnableToSerialize,
+
+    // Reconnecting errors
+    #[error("Gave up reconnecting after too many failed attempts")]
+    ReconnectGaveUp,
 
     // embedded_io errors
     #[error("{0:?}")]
     Io(embedded_io::ErrorKind),
+    #[error("Error occured while reading from stream: {0}")]
+    ReadFailed(String),
+    #[error("Error occured while writing to stream: {0}")]
+    WriteFailed(String),
+    #[error("Error occured while flushing stream: {0}")]
+    FlushFailed(String),
 
     // Tls errors during IO
     #[cfg(feature = "tls")]
@@ -1,5 +1,7 @@
 //! A no_std implementation of https://github.com/tokio-rs/tokio/blob/master/tokio-util/src/codec/framed_impl.rs
 
+#[cfg(feature = "std")]
+use super::super::io::io_slice::as_mut_std_io_slices;
 use super::super::io::{io_slice::IoSlice, AsyncRead, AsyncWrite};
 use super::codec::{Decoder, Encoder};
 use super::errors::IoError;
@@ -144,7 +146,7 @@ where
                 if state.eof {
                     return match pinned.codec.decode_eof(&mut state.buffer) {
                         Err(err) => {
-                            state.has_errored = true;
+                            state.has_errored = !pinned.codec.is_recoverable(&err);
                             Poll::Ready(Some(Err!(err)))
                         }
                         Ok(frame) => {
@@ -158,7 +160,9 @@ where
 
                 if let Some(frame) = match pinned.codec.decode(&mut state.buffer) {
                     Err(err) => {
-                        state.has_errored = true;
+                        // Recoverable errors are yielded, then decoding goes on with the
+                        // bytes after the corrupt frame.
+                        state.has_errored = !pinned.codec.is_recoverable(&err);
                         return Poll::Ready(Some(Err!(err)));
                     }
                     Ok(frame) => frame,
@@ -262,7 +266,7 @@ where
     }
 }
 
-pub fn poll_read_buf<T: AsyncRead, B: BufMut>(
+pub fn poll_read_buf<T: AsyncRead + ?Sized, B: BufMut>(
     io: Pin<&mut T>,
     cx: &mut Context<'_>,
     buf: &mut B,
@@ -295,13 +299,14 @@ pub fn poll_read_buf<T: AsyncRead, B: BufMut>(
     Poll::Ready(Ok(n))
 }
 
-pub fn poll_write_buf<T: AsyncWrite, B: Buf>(
+/// The maximum number of chunks gathered into a single vectored write.
+const MAX_BUFS: usize = 64;
+
+pub fn poll_write_buf<T: AsyncWrite + ?Sized, B: Buf>(
     io: Pin<&mut T>,
     cx: &mut Context<'_>,
     buf: &mut B,
 ) -> Poll<Result<usize>> {
-    const MAX_BUFS: usize = 64;
-
     if !buf.has_remaining() {
         return Poll::Ready(Ok(0));
     }
@@ -319,6 +324,14 @@ pub fn poll_write_buf<T: AsyncWrite, B: Buf>(
     Poll::Ready(Ok(n))
 }
 
+/// Fills `dst` with up to `dst.len()` chunks of `buf`, returning how many were filled.
+#[cfg(feature = "std")]
+fn chunks_vectored<'a, B: Buf>(buf: &'a B, dst: &mut [IoSlice<'a>]) -> usize {
+    buf.chunks_vectored(as_mut_std_io_slices(dst))
+}
+
+/// `Buf::chunks_vectored` needs `std`, so without it only the current chunk is available.
+#[cfg(not(feature = "std"))]
 fn chunks_vectored<'a, B: Buf>(buf: &'a B, dst: &mut [IoSlice<'a>]) -> usize {
     if dst.is_empty() {
         return 0;
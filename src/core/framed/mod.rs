@@ -5,6 +5,7 @@ pub mod codec;
 pub use codec::Codec;
 
 mod framed_impl;
+pub(crate) use framed_impl::{poll_read_buf, poll_write_buf};
 use framed_impl::{FramedImpl, RWFrames, ReadFrame, WriteFrame};
 
 pub mod errors;
@@ -0,0 +1,19 @@
+//! A no_std version of `tokio::io::AsyncBufRead`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio/src/io/async_buf_read.rs>`
+
+use crate::core::io::AsyncRead;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+pub trait AsyncBufRead: AsyncRead {
+    /// Returns the contents of the internal buffer, filling it with more data from the inner
+    /// reader if it is empty. An empty slice signals EOF.
+    fn poll_fill_buf(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<&[u8], Self::Error>>;
+
+    /// Marks `amt` bytes of the buffer returned by [`AsyncBufRead::poll_fill_buf`] as read.
+    fn consume(self: Pin<&mut Self>, amt: usize);
+}
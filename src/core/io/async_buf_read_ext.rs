@@ -0,0 +1,76 @@
+//! A no_std version of `tokio::io::AsyncBufReadExt`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio/src/io/util/async_buf_read_ext.rs>`
+
+use crate::core::framed::IoError;
+use crate::core::io::AsyncBufRead;
+use crate::Err;
+use alloc::string::String;
+use alloc::vec::Vec;
+use anyhow::Result;
+use core::future::poll_fn;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+
+fn poll_read_until<R: AsyncBufRead + ?Sized>(
+    mut reader: Pin<&mut R>,
+    cx: &mut Context<'_>,
+    delimiter: u8,
+    buf: &mut Vec<u8>,
+    read: &mut usize,
+) -> Poll<Result<usize>> {
+    loop {
+        let (done, used) = {
+            let available = match ready!(reader.as_mut().poll_fill_buf(cx)) {
+                Ok(available) => available,
+                Err(error) => return Poll::Ready(Err!(error)),
+            };
+            match available.iter().position(|byte| *byte == delimiter) {
+                Some(i) => {
+                    buf.extend_from_slice(&available[..=i]);
+                    (true, i + 1)
+                }
+                None => {
+                    buf.extend_from_slice(available);
+                    (false, available.len())
+                }
+            }
+        };
+        reader.as_mut().consume(used);
+        *read += used;
+
+        if done || used == 0 {
+            return Poll::Ready(Ok(core::mem::replace(read, 0)));
+        }
+    }
+}
+
+pub trait AsyncBufReadExt: AsyncBufRead {
+    /// Reads into `buf` until `delimiter` (included) or EOF, returning how many bytes were read.
+    async fn read_until(&mut self, delimiter: u8, buf: &mut Vec<u8>) -> Result<usize>
+    where
+        Self: Unpin,
+    {
+        let mut read = 0;
+        poll_fn(|cx| poll_read_until(Pin::new(&mut *self), cx, delimiter, buf, &mut read)).await
+    }
+
+    /// Reads a UTF-8 line (including the trailing `\n`) and appends it to `buf`.
+    async fn read_line(&mut self, buf: &mut String) -> Result<usize>
+    where
+        Self: Unpin,
+    {
+        let mut line = Vec::new();
+        let read = self.read_until(b'\n', &mut line).await?;
+        match core::str::from_utf8(&line) {
+            Ok(line) => {
+                buf.push_str(line);
+                Ok(read)
+            }
+            Err(_) => Err!(IoError::InvalidUtf8),
+        }
+    }
+}
+
+impl<R: AsyncBufRead + ?Sized> AsyncBufReadExt for R {}
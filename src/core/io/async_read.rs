@@ -12,6 +12,7 @@ use crate::core::io::ReadBuf;
 use tokio::io::ReadBuf;
 
 use crate::core::framed::IoError;
+use crate::core::io::io_slice::IoSliceMut;
 
 pub trait AsyncRead {
     type Error: Debug + Display;
@@ -21,6 +22,31 @@ pub trait AsyncRead {
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<Result<(), Self::Error>>;
+
+    /// Like [`AsyncRead::poll_read`], but reads into several buffers, returning how many bytes
+    /// were read. By default only the first non-empty buffer is filled.
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize, Self::Error>> {
+        let buf = bufs
+            .iter_mut()
+            .find(|b| !b.is_empty())
+            .map_or(&mut [][..], |b| &mut **b);
+        let mut buf = ReadBuf::new(buf);
+        match self.poll_read(cx, &mut buf) {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(buf.filled().len())),
+            Poll::Ready(Err(error)) => Poll::Ready(Err(error)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    /// Whether [`AsyncRead::poll_read_vectored`] is implemented more efficiently than reading
+    /// into a single buffer.
+    fn is_read_vectored(&self) -> bool {
+        false
+    }
 }
 
 macro_rules! deref_async_read {
@@ -38,6 +64,24 @@ macro_rules! deref_async_read {
                 Poll::Pending => Poll::Pending,
             }
         }
+
+        fn poll_read_vectored(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &mut [IoSliceMut<'_>],
+        ) -> Poll<Result<usize>> {
+            match Pin::new(&mut **self).poll_read_vectored(cx, bufs) {
+                Poll::Ready(result) => match result {
+                    Ok(n) => Poll::Ready(Ok(n)),
+                    Err(_) => Poll::Ready(Err!(IoError::DecodeWhileReadError)),
+                },
+                Poll::Pending => Poll::Pending,
+            }
+        }
+
+        fn is_read_vectored(&self) -> bool {
+            (**self).is_read_vectored()
+        }
     };
 }
 
@@ -73,4 +117,22 @@ where
             Poll::Pending => Poll::Pending,
         }
     }
+
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize, Self::Error>> {
+        match self.get_mut().as_mut().poll_read_vectored(cx, bufs) {
+            Poll::Ready(result) => match result {
+                Ok(n) => Poll::Ready(Ok(n)),
+                Err(err) => Poll::Ready(Err!(err)),
+            },
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn is_read_vectored(&self) -> bool {
+        (**self).is_read_vectored()
+    }
 }
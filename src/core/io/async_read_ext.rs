@@ -0,0 +1,100 @@
+//! A no_std version of `tokio::io::AsyncReadExt`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio/src/io/util/async_read_ext.rs>`
+
+use crate::core::framed::{poll_read_buf, IoError};
+use crate::core::io::{io_slice::IoSliceMut, AsyncRead, Chain, Take};
+use crate::Err;
+use alloc::vec::Vec;
+use anyhow::Result;
+use bytes::BufMut;
+use core::future::poll_fn;
+use core::pin::Pin;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+pub trait AsyncReadExt: AsyncRead {
+    /// Pulls some bytes into `buf`, returning how many bytes were read. `0` signals EOF.
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize>
+    where
+        Self: Unpin,
+    {
+        let mut buf = ReadBuf::new(buf);
+        match poll_fn(|cx| Pin::new(&mut *self).poll_read(cx, &mut buf)).await {
+            Ok(()) => Ok(buf.filled().len()),
+            Err(error) => Err!(error),
+        }
+    }
+
+    /// Like [`AsyncReadExt::read`], but reads into several buffers.
+    async fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize>
+    where
+        Self: Unpin,
+    {
+        match poll_fn(|cx| Pin::new(&mut *self).poll_read_vectored(cx, bufs)).await {
+            Ok(n) => Ok(n),
+            Err(error) => Err!(error),
+        }
+    }
+
+    /// Reads exactly `buf.len()` bytes, failing if the stream ends before.
+    async fn read_exact(&mut self, buf: &mut [u8]) -> Result<usize>
+    where
+        Self: Unpin,
+    {
+        let mut filled = 0;
+        while filled < buf.len() {
+            match self.read(&mut buf[filled..]).await? {
+                0 => return Err!(IoError::UnexpectedEof),
+                n => filled += n,
+            }
+        }
+
+        Ok(filled)
+    }
+
+    /// Reads until EOF, appending everything to `buf`.
+    async fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize>
+    where
+        Self: Unpin,
+    {
+        let start = buf.len();
+        loop {
+            if self.read_buf(buf).await? == 0 {
+                return Ok(buf.len() - start);
+            }
+        }
+    }
+
+    /// Pulls some bytes into the remaining capacity of `buf`, advancing it.
+    async fn read_buf<B: BufMut>(&mut self, buf: &mut B) -> Result<usize>
+    where
+        Self: Unpin,
+    {
+        match poll_fn(|cx| poll_read_buf(Pin::new(&mut *self), cx, buf)).await {
+            Ok(n) => Ok(n),
+            Err(error) => Err!(error),
+        }
+    }
+
+    /// Reads at most `limit` bytes, then signals EOF.
+    fn take(self, limit: u64) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take::new(self, limit)
+    }
+
+    /// Reads `self` until EOF, then `next`.
+    fn chain<R: AsyncRead>(self, next: R) -> Chain<Self, R>
+    where
+        Self: Sized,
+    {
+        Chain::new(self, next)
+    }
+}
+
+impl<R: AsyncRead + ?Sized> AsyncReadExt for R {}
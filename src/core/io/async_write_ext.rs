@@ -0,0 +1,67 @@
+//! A no_std version of `tokio::io::AsyncWriteExt`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio/src/io/util/async_write_ext.rs>`
+
+use crate::core::framed::IoError;
+use crate::core::io::{io_slice::IoSlice, AsyncWrite, Limit};
+use crate::Err;
+use anyhow::Result;
+use core::future::poll_fn;
+use core::pin::Pin;
+
+pub trait AsyncWriteExt: AsyncWrite {
+    /// Writes some bytes of `buf`, returning how many bytes were written.
+    async fn write(&mut self, buf: &[u8]) -> Result<usize>
+    where
+        Self: Unpin,
+    {
+        poll_fn(|cx| Pin::new(&mut *self).poll_write(cx, buf)).await
+    }
+
+    /// Writes the whole `buf`, failing if the stream stops accepting bytes.
+    async fn write_all(&mut self, mut buf: &[u8]) -> Result<()>
+    where
+        Self: Unpin,
+    {
+        while !buf.is_empty() {
+            match self.write(buf).await? {
+                0 => return Err!(IoError::WriteZero),
+                n => buf = &buf[n..],
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes some bytes of `bufs` in a single call, returning how many bytes were written.
+    async fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize>
+    where
+        Self: Unpin,
+    {
+        poll_fn(|cx| Pin::new(&mut *self).poll_write_vectored(cx, bufs)).await
+    }
+
+    async fn flush(&mut self) -> Result<()>
+    where
+        Self: Unpin,
+    {
+        poll_fn(|cx| Pin::new(&mut *self).poll_flush(cx)).await
+    }
+
+    async fn shutdown(&mut self) -> Result<()>
+    where
+        Self: Unpin,
+    {
+        poll_fn(|cx| Pin::new(&mut *self).poll_shutdown(cx)).await
+    }
+
+    /// Writes at most `limit` bytes, failing once the limit is exhausted.
+    fn limit(self, limit: u64) -> Limit<Self>
+    where
+        Self: Sized,
+    {
+        Limit::new(self, limit)
+    }
+}
+
+impl<W: AsyncWrite + ?Sized> AsyncWriteExt for W {}
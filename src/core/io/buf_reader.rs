@@ -0,0 +1,151 @@
+//! A no_std version of `tokio::io::BufReader`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio/src/io/util/buf_reader.rs>`
+
+use crate::core::io::io_slice::IoSlice;
+use crate::core::io::{AsyncBufRead, AsyncRead, AsyncWrite};
+use alloc::boxed::Box;
+use alloc::vec;
+use anyhow::Result;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use pin_project_lite::pin_project;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+pub(crate) const DEFAULT_BUF_SIZE: usize = 8 * 1024;
+
+pin_project! {
+    /// Buffers reads from `inner`. The buffer is either heap allocated (`Box<[u8]>`) or
+    /// supplied by the caller (e.g. `&mut [u8]`) for builds without an allocator.
+    #[derive(Debug)]
+    pub struct BufReader<R, B = Box<[u8]>> {
+        #[pin]
+        inner: R,
+        buf: B,
+        pos: usize,
+        cap: usize,
+    }
+}
+
+impl<R> BufReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_capacity(DEFAULT_BUF_SIZE, inner)
+    }
+
+    pub fn with_capacity(capacity: usize, inner: R) -> Self {
+        Self::with_buffer(inner, vec![0; capacity].into_boxed_slice())
+    }
+}
+
+impl<R, B: AsRef<[u8]> + AsMut<[u8]>> BufReader<R, B> {
+    pub fn with_buffer(inner: R, buf: B) -> Self {
+        Self {
+            inner,
+            buf,
+            pos: 0,
+            cap: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut R> {
+        self.project().inner
+    }
+
+    /// Returns the buffered but not yet consumed bytes.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf.as_ref()[self.pos..self.cap]
+    }
+
+    /// Returns the inner reader. Buffered data is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead, B: AsRef<[u8]> + AsMut<[u8]>> AsyncRead for BufReader<R, B> {
+    type Error = R::Error;
+
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        // Bypass our buffer if it is empty and the caller's buffer is at least as large.
+        if self.pos == self.cap && buf.remaining() >= self.buf.as_ref().len() {
+            let result = ready!(self.as_mut().get_pin_mut().poll_read(cx, buf));
+            let me = self.project();
+            *me.pos = 0;
+            *me.cap = 0;
+            return Poll::Ready(result);
+        }
+
+        let available = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let amt = core::cmp::min(available.len(), buf.remaining());
+        buf.put_slice(&available[..amt]);
+        self.consume(amt);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R: AsyncRead, B: AsRef<[u8]> + AsMut<[u8]>> AsyncBufRead for BufReader<R, B> {
+    fn poll_fill_buf(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<&[u8], Self::Error>> {
+        let me = self.project();
+
+        if *me.pos >= *me.cap {
+            let mut buf = ReadBuf::new(me.buf.as_mut());
+            ready!(me.inner.poll_read(cx, &mut buf))?;
+            *me.cap = buf.filled().len();
+            *me.pos = 0;
+        }
+
+        Poll::Ready(Ok(&me.buf.as_ref()[*me.pos..*me.cap]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let me = self.project();
+        *me.pos = core::cmp::min(*me.pos + amt, *me.cap);
+    }
+}
+
+impl<R: AsyncWrite, B> AsyncWrite for BufReader<R, B> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        self.project().inner.poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<Result<usize>> {
+        self.project().inner.poll_write_vectored(cx, bufs)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
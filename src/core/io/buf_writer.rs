@@ -0,0 +1,156 @@
+//! A no_std version of `tokio::io::BufWriter`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio/src/io/util/buf_writer.rs>`
+
+use crate::core::framed::IoError;
+use crate::core::io::buf_reader::DEFAULT_BUF_SIZE;
+use crate::core::io::{AsyncBufRead, AsyncRead, AsyncWrite};
+use crate::Err;
+use alloc::boxed::Box;
+use alloc::vec;
+use anyhow::Result;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use pin_project_lite::pin_project;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+pin_project! {
+    /// Buffers writes to `inner` until the buffer is full or the writer is flushed. The buffer
+    /// is either heap allocated (`Box<[u8]>`) or supplied by the caller (e.g. `&mut [u8]`) for
+    /// builds without an allocator.
+    #[derive(Debug)]
+    pub struct BufWriter<W, B = Box<[u8]>> {
+        #[pin]
+        inner: W,
+        buf: B,
+        len: usize,
+        written: usize,
+    }
+}
+
+impl<W> BufWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self::with_capacity(DEFAULT_BUF_SIZE, inner)
+    }
+
+    pub fn with_capacity(capacity: usize, inner: W) -> Self {
+        Self::with_buffer(inner, vec![0; capacity].into_boxed_slice())
+    }
+}
+
+impl<W, B: AsRef<[u8]> + AsMut<[u8]>> BufWriter<W, B> {
+    pub fn with_buffer(inner: W, buf: B) -> Self {
+        Self {
+            inner,
+            buf,
+            len: 0,
+            written: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut W> {
+        self.project().inner
+    }
+
+    /// Returns the buffered but not yet written bytes.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf.as_ref()[self.written..self.len]
+    }
+
+    /// Returns the inner writer. Buffered data is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite, B: AsRef<[u8]> + AsMut<[u8]>> BufWriter<W, B> {
+    fn poll_flush_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let mut me = self.project();
+
+        while *me.written < *me.len {
+            match ready!(me
+                .inner
+                .as_mut()
+                .poll_write(cx, &me.buf.as_ref()[*me.written..*me.len]))
+            {
+                Ok(0) => return Poll::Ready(Err!(IoError::WriteZero)),
+                Ok(n) => *me.written += n,
+                Err(error) => return Poll::Ready(Err(error)),
+            }
+        }
+        *me.written = 0;
+        *me.len = 0;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite, B: AsRef<[u8]> + AsMut<[u8]>> AsyncWrite for BufWriter<W, B> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>> {
+        let capacity = self.buf.as_ref().len();
+        if self.len + buf.len() > capacity {
+            ready!(self.as_mut().poll_flush_buf(cx))?;
+        }
+
+        let me = self.project();
+        if buf.len() >= capacity {
+            me.inner.poll_write(cx, buf)
+        } else {
+            me.buf.as_mut()[*me.len..*me.len + buf.len()].copy_from_slice(buf);
+            *me.len += buf.len();
+            Poll::Ready(Ok(buf.len()))
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        ready!(self.as_mut().poll_flush_buf(cx))?;
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        ready!(self.as_mut().poll_flush_buf(cx))?;
+        self.project().inner.poll_shutdown(cx)
+    }
+}
+
+impl<W: AsyncRead, B> AsyncRead for BufWriter<W, B> {
+    type Error = W::Error;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_read(cx, buf)
+    }
+}
+
+impl<W: AsyncBufRead, B> AsyncBufRead for BufWriter<W, B> {
+    fn poll_fill_buf(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<&[u8], Self::Error>> {
+        self.project().inner.poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.project().inner.consume(amt)
+    }
+}
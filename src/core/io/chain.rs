@@ -0,0 +1,114 @@
+//! A no_std version of `tokio::io::Chain`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio/src/io/util/chain.rs>`
+
+use crate::core::io::{AsyncBufRead, AsyncRead};
+use crate::Err;
+use anyhow::Result;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use pin_project_lite::pin_project;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+pin_project! {
+    /// Reads `first` until EOF, then `second`.
+    /// Created by [`AsyncReadExt::chain`](crate::core::io::AsyncReadExt::chain).
+    #[derive(Debug)]
+    pub struct Chain<T, U> {
+        #[pin]
+        first: T,
+        #[pin]
+        second: U,
+        done_first: bool,
+    }
+}
+
+impl<T, U> Chain<T, U> {
+    pub fn new(first: T, second: U) -> Self {
+        Self {
+            first,
+            second,
+            done_first: false,
+        }
+    }
+
+    pub fn get_ref(&self) -> (&T, &U) {
+        (&self.first, &self.second)
+    }
+
+    pub fn get_mut(&mut self) -> (&mut T, &mut U) {
+        (&mut self.first, &mut self.second)
+    }
+
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> (Pin<&mut T>, Pin<&mut U>) {
+        let me = self.project();
+        (me.first, me.second)
+    }
+
+    pub fn into_inner(self) -> (T, U) {
+        (self.first, self.second)
+    }
+}
+
+impl<T: AsyncRead, U: AsyncRead> AsyncRead for Chain<T, U> {
+    // Both readers may fail with different errors.
+    type Error = anyhow::Error;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        let me = self.project();
+
+        if !*me.done_first {
+            let remaining = buf.remaining();
+            match ready!(me.first.poll_read(cx, buf)) {
+                Ok(()) if remaining == buf.remaining() && remaining != 0 => *me.done_first = true,
+                Ok(()) => return Poll::Ready(Ok(())),
+                Err(error) => return Poll::Ready(Err!(error)),
+            }
+        }
+
+        match ready!(me.second.poll_read(cx, buf)) {
+            Ok(()) => Poll::Ready(Ok(())),
+            Err(error) => Poll::Ready(Err!(error)),
+        }
+    }
+}
+
+impl<T: AsyncBufRead, U: AsyncBufRead> AsyncBufRead for Chain<T, U> {
+    fn poll_fill_buf(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<&[u8], Self::Error>> {
+        let me = self.project();
+
+        if !*me.done_first {
+            match ready!(me.first.poll_fill_buf(cx)) {
+                Ok([]) => *me.done_first = true,
+                Ok(buf) => return Poll::Ready(Ok(buf)),
+                Err(error) => return Poll::Ready(Err!(error)),
+            }
+        }
+
+        match ready!(me.second.poll_fill_buf(cx)) {
+            Ok(buf) => Poll::Ready(Ok(buf)),
+            Err(error) => Poll::Ready(Err!(error)),
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let me = self.project();
+        if !*me.done_first {
+            me.first.consume(amt)
+        } else {
+            me.second.consume(amt)
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! Compatibility between [`AsyncRead`]/[`AsyncWrite`] and `embedded_io::asynch::{Read, Write}`.
+
+use crate::core::framed::IoError;
+use crate::core::io::{AsyncRead, AsyncWrite};
+use crate::Err;
+use alloc::string::ToString;
+use anyhow::Result;
+use core::future::{poll_fn, Future};
+use core::pin::{pin, Pin};
+use core::task::{Context, Poll};
+use embedded_io::asynch::{Read, Write};
+use embedded_io::{Error, Io};
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+/// Marks `embedded_io` transports whose `read`, `write` and `flush` futures can be dropped
+/// while pending without losing data, which [`FromEmbeddedIo`] relies on.
+///
+/// Transports that only copy from and to an internal buffer once it is ready are cancel-safe,
+/// e.g. `embassy-net` sockets or ring buffered UART drivers. DMA based UART reads usually are
+/// not, the bytes already transferred are lost when the read is dropped.
+pub trait CancelSafe {}
+
+impl<T: CancelSafe + ?Sized> CancelSafe for &mut T {}
+
+impl CancelSafe for embassy_net::tcp::TcpSocket<'_> {}
+
+impl CancelSafe for embassy_net::tcp::TcpReader<'_> {}
+
+impl CancelSafe for embassy_net::tcp::TcpWriter<'_> {}
+
+/// Polls a single `embedded_io` read. The read future is dropped if it is pending, so the
+/// transport has to be [`CancelSafe`].
+pub(crate) fn poll_embedded_read<T: Read>(
+    inner: &mut T,
+    cx: &mut Context<'_>,
+    buf: &mut ReadBuf<'_>,
+) -> Poll<Result<(), IoError>> {
+    let result = {
+        let read = pin!(inner.read(buf.initialize_unfilled()));
+        read.poll(cx)
+    };
+
+    match result {
+        Poll::Ready(Ok(n)) => {
+            buf.advance(n);
+            Poll::Ready(Ok(()))
+        }
+        Poll::Ready(Err(error)) => Poll::Ready(Err(IoError::Io(error.kind()))),
+        Poll::Pending => Poll::Pending,
+    }
+}
+
+/// Polls a single `embedded_io` write. See [`poll_embedded_read`] for the cancel-safety
+/// requirement.
+pub(crate) fn poll_embedded_write<T: Write>(
+    inner: &mut T,
+    cx: &mut Context<'_>,
+    buf: &[u8],
+) -> Poll<Result<usize>> {
+    match pin!(inner.write(buf)).poll(cx) {
+        Poll::Ready(Ok(n)) => Poll::Ready(Ok(n)),
+        Poll::Ready(Err(error)) => Poll::Ready(Err!(IoError::Io(error.kind()))),
+        Poll::Pending => Poll::Pending,
+    }
+}
+
+/// Polls a single `embedded_io` flush. See [`poll_embedded_read`] for the cancel-safety
+/// requirement.
+pub(crate) fn poll_embedded_flush<T: Write>(
+    inner: &mut T,
+    cx: &mut Context<'_>,
+) -> Poll<Result<()>> {
+    match pin!(inner.flush()).poll(cx) {
+        Poll::Ready(Ok(())) => Poll::Ready(Ok(())),
+        Poll::Ready(Err(error)) => Poll::Ready(Err!(IoError::Io(error.kind()))),
+        Poll::Pending => Poll::Pending,
+    }
+}
+
+/// Turns an `embedded_io` transport (e.g. an `embassy-net` socket or an UART driver) into an
+/// [`AsyncRead`]/[`AsyncWrite`] stream, so it can be used with `Framed`.
+///
+/// Every poll creates a fresh `read`/`write` future and drops it if it is pending, therefore
+/// the transport has to be [`CancelSafe`].
+#[derive(Debug)]
+pub struct FromEmbeddedIo<T> {
+    inner: T,
+}
+
+impl<T> FromEmbeddedIo<T> {
+    pub fn new(inner: T) -> Self
+    where
+        T: CancelSafe,
+    {
+        Self { inner }
+    }
+
+    /// Like [`FromEmbeddedIo::new`], for cancel-safe transports of other crates, which can't
+    /// implement [`CancelSafe`]. Wrapping a transport that isn't cancel-safe loses data.
+    pub fn assume_cancel_safe(inner: T) -> Self {
+        Self { inner }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Read + Unpin> AsyncRead for FromEmbeddedIo<T> {
+    type Error = IoError;
+
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        poll_embedded_read(&mut self.inner, cx, buf)
+    }
+}
+
+impl<T: Write + Unpin> AsyncWrite for FromEmbeddedIo<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>> {
+        poll_embedded_write(&mut self.inner, cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        poll_embedded_flush(&mut self.inner, cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        // `embedded_io` has no notion of shutting down, flushing is all we can do.
+        poll_embedded_flush(&mut self.inner, cx)
+    }
+}
+
+/// Turns an [`AsyncRead`]/[`AsyncWrite`] stream into an `embedded_io` transport, so it can be
+/// used with `embedded-tls` or `reqwless`.
+#[derive(Debug, Default)]
+pub struct ToEmbeddedIo<T> {
+    inner: T,
+}
+
+impl<T> ToEmbeddedIo<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> Io for ToEmbeddedIo<T> {
+    type Error = IoError;
+}
+
+/// Polling an [`AsyncRead`]/[`AsyncWrite`] stream only has an effect once it is ready.
+impl<T> CancelSafe for ToEmbeddedIo<T> {}
+
+impl<T: AsyncRead + Unpin> Read for ToEmbeddedIo<T> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let mut buf = ReadBuf::new(buf);
+        poll_fn(|cx| Pin::new(&mut self.inner).poll_read(cx, &mut buf))
+            .await
+            .map_err(|error| IoError::ReadFailed(error.to_string()))?;
+
+        Ok(buf.filled().len())
+    }
+}
+
+impl<T: AsyncWrite + Unpin> Write for ToEmbeddedIo<T> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        poll_fn(|cx| Pin::new(&mut self.inner).poll_write(cx, buf))
+            .await
+            .map_err(|error| IoError::WriteFailed(error.to_string()))
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        poll_fn(|cx| Pin::new(&mut self.inner).poll_flush(cx))
+            .await
+            .map_err(|error| IoError::FlushFailed(error.to_string()))
+    }
+}
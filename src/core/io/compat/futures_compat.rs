@@ -0,0 +1,185 @@
+//! Compatibility between [`AsyncRead`]/[`AsyncWrite`] and `futures::io::{AsyncRead, AsyncWrite}`.
+
+use crate::core::io::io_slice::{
+    as_std_io_slices, as_std_io_slices_mut, from_std_io_slices, from_std_io_slices_mut, IoSlice,
+    IoSliceMut,
+};
+use crate::core::io::{AsyncRead, AsyncWrite};
+use crate::Err;
+use alloc::io;
+use alloc::string::ToString;
+use anyhow::Result;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::io::{AsyncRead as FuturesAsyncRead, AsyncWrite as FuturesAsyncWrite};
+use futures::ready;
+use pin_project_lite::pin_project;
+use tokio::io::ReadBuf;
+
+pin_project! {
+    /// Wraps a `futures::io` stream to implement [`AsyncRead`]/[`AsyncWrite`], or one of our
+    /// streams to implement `futures::io::{AsyncRead, AsyncWrite}`.
+    #[derive(Debug, Default)]
+    pub struct FuturesCompat<T> {
+        #[pin]
+        inner: T,
+    }
+}
+
+impl<T> FuturesCompat<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut T> {
+        self.project().inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+fn to_io_error(error: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error.to_string())
+}
+
+impl<T: FuturesAsyncRead> AsyncRead for FuturesCompat<T> {
+    type Error = anyhow::Error;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        match ready!(self
+            .project()
+            .inner
+            .poll_read(cx, buf.initialize_unfilled()))
+        {
+            Ok(n) => {
+                buf.advance(n);
+                Poll::Ready(Ok(()))
+            }
+            Err(error) => Poll::Ready(Err!(error)),
+        }
+    }
+
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize, Self::Error>> {
+        match ready!(self
+            .project()
+            .inner
+            .poll_read_vectored(cx, as_std_io_slices_mut(bufs)))
+        {
+            Ok(n) => Poll::Ready(Ok(n)),
+            Err(error) => Poll::Ready(Err!(error)),
+        }
+    }
+}
+
+impl<T: FuturesAsyncWrite> AsyncWrite for FuturesCompat<T> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        match ready!(self.project().inner.poll_write(cx, buf)) {
+            Ok(size) => Poll::Ready(Ok(size)),
+            Err(error) => Poll::Ready(Err!(error)),
+        }
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<Result<usize>> {
+        match ready!(self
+            .project()
+            .inner
+            .poll_write_vectored(cx, as_std_io_slices(bufs)))
+        {
+            Ok(size) => Poll::Ready(Ok(size)),
+            Err(error) => Poll::Ready(Err!(error)),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match ready!(self.project().inner.poll_flush(cx)) {
+            Ok(()) => Poll::Ready(Ok(())),
+            Err(error) => Poll::Ready(Err!(error)),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match ready!(self.project().inner.poll_close(cx)) {
+            Ok(()) => Poll::Ready(Ok(())),
+            Err(error) => Poll::Ready(Err!(error)),
+        }
+    }
+}
+
+impl<T: AsyncRead> FuturesAsyncRead for FuturesCompat<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut buf = ReadBuf::new(buf);
+        match ready!(self.project().inner.poll_read(cx, &mut buf)) {
+            Ok(()) => Poll::Ready(Ok(buf.filled().len())),
+            Err(error) => Poll::Ready(Err(to_io_error(error))),
+        }
+    }
+
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [io::IoSliceMut<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.project()
+            .inner
+            .poll_read_vectored(cx, from_std_io_slices_mut(bufs))
+            .map_err(to_io_error)
+    }
+}
+
+impl<T: AsyncWrite> FuturesAsyncWrite for FuturesCompat<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.project()
+            .inner
+            .poll_write(cx, buf)
+            .map_err(to_io_error)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.project()
+            .inner
+            .poll_write_vectored(cx, from_std_io_slices(bufs))
+            .map_err(to_io_error)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_flush(cx).map_err(to_io_error)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_shutdown(cx).map_err(to_io_error)
+    }
+}
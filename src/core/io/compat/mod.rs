@@ -0,0 +1,16 @@
+//! Wrappers to use streams implementing foreign I/O traits as [`AsyncRead`](super::AsyncRead)/
+//! [`AsyncWrite`](super::AsyncWrite) and vice versa.
+
+mod embedded_io_compat;
+pub(crate) use embedded_io_compat::{poll_embedded_flush, poll_embedded_read, poll_embedded_write};
+pub use embedded_io_compat::{CancelSafe, FromEmbeddedIo, ToEmbeddedIo};
+
+#[cfg(feature = "std")]
+mod futures_compat;
+#[cfg(feature = "std")]
+pub use futures_compat::FuturesCompat;
+
+#[cfg(feature = "std")]
+mod tokio_compat;
+#[cfg(feature = "std")]
+pub use tokio_compat::TokioCompat;
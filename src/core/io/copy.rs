@@ -0,0 +1,182 @@
+//! A no_std version of `tokio::io::copy` and `tokio::io::copy_bidirectional`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio/src/io/util/copy.rs>`
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio/src/io/util/copy_bidirectional.rs>`
+//!
+//! Both take caller supplied buffers, so they can run without an allocator.
+
+use crate::core::framed::IoError;
+use crate::core::io::{AsyncRead, AsyncWrite};
+use crate::Err;
+use anyhow::Result;
+use core::future::poll_fn;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+struct CopyBuffer<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+    cap: usize,
+    amt: u64,
+    read_done: bool,
+    need_flush: bool,
+}
+
+impl<'a> CopyBuffer<'a> {
+    fn new(buf: &'a mut [u8]) -> Self {
+        assert!(!buf.is_empty(), "copy buffer must not be empty");
+        Self {
+            buf,
+            pos: 0,
+            cap: 0,
+            amt: 0,
+            read_done: false,
+            need_flush: false,
+        }
+    }
+
+    fn poll_copy<R, W>(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut reader: Pin<&mut R>,
+        mut writer: Pin<&mut W>,
+    ) -> Poll<Result<u64>>
+    where
+        R: AsyncRead + ?Sized,
+        W: AsyncWrite + ?Sized,
+    {
+        loop {
+            // Refill the buffer once everything read so far has been written.
+            if self.pos == self.cap && !self.read_done {
+                let mut buf = ReadBuf::new(self.buf);
+                match reader.as_mut().poll_read(cx, &mut buf) {
+                    Poll::Ready(Ok(())) => {
+                        let n = buf.filled().len();
+                        if n == 0 {
+                            self.read_done = true;
+                        } else {
+                            self.pos = 0;
+                            self.cap = n;
+                        }
+                    }
+                    Poll::Ready(Err(error)) => return Poll::Ready(Err!(error)),
+                    Poll::Pending => {
+                        // Don't hold back already written bytes while waiting for new ones.
+                        if self.need_flush {
+                            ready!(writer.as_mut().poll_flush(cx))?;
+                            self.need_flush = false;
+                        }
+                        return Poll::Pending;
+                    }
+                }
+            }
+
+            while self.pos < self.cap {
+                match ready!(writer
+                    .as_mut()
+                    .poll_write(cx, &self.buf[self.pos..self.cap]))?
+                {
+                    0 => return Poll::Ready(Err!(IoError::WriteZero)),
+                    n => {
+                        self.pos += n;
+                        self.amt += n as u64;
+                        self.need_flush = true;
+                    }
+                }
+            }
+
+            if self.read_done && self.pos == self.cap {
+                ready!(writer.as_mut().poll_flush(cx))?;
+                self.need_flush = false;
+                return Poll::Ready(Ok(self.amt));
+            }
+        }
+    }
+}
+
+/// Copies everything from `reader` to `writer` until EOF, using `buf` as intermediate
+/// storage, and returns how many bytes were copied. `writer` is flushed but not shut down.
+///
+/// # Panics
+///
+/// If `buf` is empty.
+pub async fn copy<R, W>(reader: &mut R, writer: &mut W, buf: &mut [u8]) -> Result<u64>
+where
+    R: AsyncRead + Unpin + ?Sized,
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    let mut copy_buf = CopyBuffer::new(buf);
+    poll_fn(|cx| copy_buf.poll_copy(cx, Pin::new(&mut *reader), Pin::new(&mut *writer))).await
+}
+
+enum TransferState<'a> {
+    Running(CopyBuffer<'a>),
+    ShuttingDown(u64),
+    Done(u64),
+}
+
+fn poll_transfer<R, W>(
+    cx: &mut Context<'_>,
+    state: &mut TransferState<'_>,
+    mut reader: Pin<&mut R>,
+    mut writer: Pin<&mut W>,
+) -> Poll<Result<u64>>
+where
+    R: AsyncRead + ?Sized,
+    W: AsyncWrite + ?Sized,
+{
+    loop {
+        match state {
+            TransferState::Running(buf) => {
+                let count = ready!(buf.poll_copy(cx, reader.as_mut(), writer.as_mut()))?;
+                *state = TransferState::ShuttingDown(count);
+            }
+            TransferState::ShuttingDown(count) => {
+                // Propagate the half-close to the other side.
+                ready!(writer.as_mut().poll_shutdown(cx))?;
+                *state = TransferState::Done(*count);
+            }
+            TransferState::Done(count) => return Poll::Ready(Ok(*count)),
+        }
+    }
+}
+
+/// Copies data in both directions between `a` and `b` until both reached EOF, using `buf_a`
+/// for `a -> b` and `buf_b` for `b -> a`. Once one side reaches EOF, the other side's write
+/// half is shut down while the opposite direction keeps running.
+///
+/// Returns the number of bytes copied `a -> b` and `b -> a`.
+///
+/// # Panics
+///
+/// If one of the buffers is empty.
+pub async fn copy_bidirectional<A, B>(
+    a: &mut A,
+    b: &mut B,
+    buf_a: &mut [u8],
+    buf_b: &mut [u8],
+) -> Result<(u64, u64)>
+where
+    A: AsyncRead + AsyncWrite + Unpin + ?Sized,
+    B: AsyncRead + AsyncWrite + Unpin + ?Sized,
+{
+    let mut a_to_b = TransferState::Running(CopyBuffer::new(buf_a));
+    let mut b_to_a = TransferState::Running(CopyBuffer::new(buf_b));
+
+    poll_fn(|cx| {
+        let a_to_b = poll_transfer(cx, &mut a_to_b, Pin::new(&mut *a), Pin::new(&mut *b))?;
+        let b_to_a = poll_transfer(cx, &mut b_to_a, Pin::new(&mut *b), Pin::new(&mut *a))?;
+
+        match (a_to_b, b_to_a) {
+            (Poll::Ready(a_to_b), Poll::Ready(b_to_a)) => Poll::Ready(Ok((a_to_b, b_to_a))),
+            _ => Poll::Pending,
+        }
+    })
+    .await
+}
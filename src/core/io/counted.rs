@@ -0,0 +1,147 @@
+//! Counts the bytes read from and written to a stream.
+
+use crate::core::io::io_slice::{IoSlice, IoSliceMut};
+use crate::core::io::{AsyncBufRead, AsyncRead, AsyncWrite};
+use anyhow::Result;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use pin_project_lite::pin_project;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+pin_project! {
+    /// Passes everything through to `inner` while counting the bytes read and written.
+    #[derive(Debug, Default)]
+    pub struct Counted<T> {
+        #[pin]
+        inner: T,
+        bytes_read: u64,
+        bytes_written: u64,
+    }
+}
+
+impl<T> Counted<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            bytes_read: 0,
+            bytes_written: 0,
+        }
+    }
+
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Resets both counters to zero.
+    pub fn reset(&mut self) {
+        self.bytes_read = 0;
+        self.bytes_written = 0;
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut T> {
+        self.project().inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: AsyncRead> AsyncRead for Counted<T> {
+    type Error = T::Error;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        let me = self.project();
+        let filled = buf.filled().len();
+        ready!(me.inner.poll_read(cx, buf))?;
+        *me.bytes_read += (buf.filled().len() - filled) as u64;
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize, Self::Error>> {
+        let me = self.project();
+        let n = ready!(me.inner.poll_read_vectored(cx, bufs))?;
+        *me.bytes_read += n as u64;
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn is_read_vectored(&self) -> bool {
+        self.inner.is_read_vectored()
+    }
+}
+
+impl<T: AsyncBufRead> AsyncBufRead for Counted<T> {
+    fn poll_fill_buf(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<&[u8], Self::Error>> {
+        self.project().inner.poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let me = self.project();
+        *me.bytes_read += amt as u64;
+        me.inner.consume(amt)
+    }
+}
+
+impl<T: AsyncWrite> AsyncWrite for Counted<T> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        let me = self.project();
+        let n = ready!(me.inner.poll_write(cx, buf))?;
+        *me.bytes_written += n as u64;
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<Result<usize>> {
+        let me = self.project();
+        let n = ready!(me.inner.poll_write_vectored(cx, bufs))?;
+        *me.bytes_written += n as u64;
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
@@ -0,0 +1,270 @@
+//! A no_std version of `tokio::io::duplex`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio/src/io/util/mem.rs>`
+//!
+//! Creates two connected in-memory streams, which is handy to test codecs, `Framed`, TLS or
+//! websocket logic without a socket. Data written to one end can be read from the other one.
+//! Shutting down or dropping one end lets the other end read EOF once the buffered data is
+//! drained; writes to a closed pipe fail with [`IoError::BrokenPipe`].
+
+use crate::core::framed::IoError;
+use crate::core::io::{AsyncRead, AsyncWrite};
+use crate::utils::lock::Lock;
+use crate::Err;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use anyhow::Result;
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use heapless::Deque;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+/// Creates a pair of connected streams, each buffering up to `max_buf_size` bytes written to
+/// it. The buffers are allocated lazily and grow up to that size.
+pub fn duplex(max_buf_size: usize) -> (DuplexStream, DuplexStream) {
+    DuplexStream::pair(
+        GrowableBuffer::new(max_buf_size),
+        GrowableBuffer::new(max_buf_size),
+    )
+}
+
+/// Creates a pair of connected streams, each using a fixed ring buffer of `N` bytes.
+pub fn duplex_fixed<const N: usize>() -> (DuplexStream<Deque<u8, N>>, DuplexStream<Deque<u8, N>>) {
+    DuplexStream::pair(Deque::new(), Deque::new())
+}
+
+/// The buffer a [`DuplexStream`] writes into and its peer reads from.
+pub trait PipeBuffer {
+    /// Appends as many bytes of `buf` as fit and returns how many were written.
+    fn push_slice(&mut self, buf: &[u8]) -> usize;
+
+    /// Moves as many buffered bytes as fit into `buf` and returns how many were read.
+    fn pop_slice(&mut self, buf: &mut [u8]) -> usize;
+
+    fn is_empty(&self) -> bool;
+
+    fn is_full(&self) -> bool;
+}
+
+/// A heap allocated [`PipeBuffer`] holding at most `max_size` bytes.
+#[derive(Debug)]
+pub struct GrowableBuffer {
+    buf: VecDeque<u8>,
+    max_size: usize,
+}
+
+impl GrowableBuffer {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            buf: VecDeque::new(),
+            max_size,
+        }
+    }
+}
+
+impl PipeBuffer for GrowableBuffer {
+    fn push_slice(&mut self, buf: &[u8]) -> usize {
+        let len = core::cmp::min(buf.len(), self.max_size - self.buf.len());
+        self.buf.extend(&buf[..len]);
+        len
+    }
+
+    fn pop_slice(&mut self, buf: &mut [u8]) -> usize {
+        let len = core::cmp::min(buf.len(), self.buf.len());
+        for (dst, src) in buf.iter_mut().zip(self.buf.drain(..len)) {
+            *dst = src;
+        }
+        len
+    }
+
+    fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    fn is_full(&self) -> bool {
+        self.buf.len() >= self.max_size
+    }
+}
+
+impl<const N: usize> PipeBuffer for Deque<u8, N> {
+    fn push_slice(&mut self, buf: &[u8]) -> usize {
+        let mut len = 0;
+        for byte in buf {
+            if self.push_back(*byte).is_err() {
+                break;
+            }
+            len += 1;
+        }
+        len
+    }
+
+    fn pop_slice(&mut self, buf: &mut [u8]) -> usize {
+        let mut len = 0;
+        for dst in buf.iter_mut() {
+            match self.pop_front() {
+                Some(byte) => *dst = byte,
+                None => break,
+            }
+            len += 1;
+        }
+        len
+    }
+
+    fn is_empty(&self) -> bool {
+        Deque::is_empty(self)
+    }
+
+    fn is_full(&self) -> bool {
+        Deque::is_full(self)
+    }
+}
+
+/// One end of an in-memory pipe created by [`duplex`] or [`duplex_fixed`].
+pub struct DuplexStream<B = GrowableBuffer> {
+    read: Arc<Lock<Pipe<B>>>,
+    write: Arc<Lock<Pipe<B>>>,
+}
+
+impl<B: PipeBuffer> DuplexStream<B> {
+    fn pair(a_to_b: B, b_to_a: B) -> (Self, Self) {
+        let a_to_b = Arc::new(Lock::new(Pipe::new(a_to_b)));
+        let b_to_a = Arc::new(Lock::new(Pipe::new(b_to_a)));
+
+        (
+            Self {
+                read: b_to_a.clone(),
+                write: a_to_b.clone(),
+            },
+            Self {
+                read: a_to_b,
+                write: b_to_a,
+            },
+        )
+    }
+}
+
+impl<B> Drop for DuplexStream<B> {
+    fn drop(&mut self) {
+        // The peer reads EOF and its writes fail from now on.
+        self.write.lock().close();
+        self.read.lock().close();
+    }
+}
+
+impl<B> fmt::Debug for DuplexStream<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DuplexStream").finish()
+    }
+}
+
+impl<B: PipeBuffer> AsyncRead for DuplexStream<B> {
+    type Error = IoError;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.read.lock().poll_read(cx, buf)
+    }
+}
+
+impl<B: PipeBuffer> AsyncWrite for DuplexStream<B> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        self.write.lock().poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.write.lock().close();
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A unidirectional pipe. Only the writing end parks a `write_waker` and only the reading
+/// end parks a `read_waker`.
+struct Pipe<B> {
+    buf: B,
+    is_closed: bool,
+    read_waker: Option<Waker>,
+    write_waker: Option<Waker>,
+}
+
+impl<B> Pipe<B> {
+    fn new(buf: B) -> Self {
+        Self {
+            buf,
+            is_closed: false,
+            read_waker: None,
+            write_waker: None,
+        }
+    }
+
+    fn close(&mut self) {
+        self.is_closed = true;
+        if let Some(waker) = self.read_waker.take() {
+            waker.wake();
+        }
+        if let Some(waker) = self.write_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<B: PipeBuffer> Pipe<B> {
+    fn poll_read(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), IoError>> {
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        if self.buf.is_empty() {
+            if self.is_closed {
+                // EOF
+                return Poll::Ready(Ok(()));
+            }
+            self.read_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let read = self.buf.pop_slice(buf.initialize_unfilled());
+        buf.advance(read);
+        if let Some(waker) = self.write_waker.take() {
+            waker.wake();
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_write(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        if self.is_closed {
+            return Poll::Ready(Err!(IoError::BrokenPipe));
+        }
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        if self.buf.is_full() {
+            self.write_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let written = self.buf.push_slice(buf);
+        if let Some(waker) = self.read_waker.take() {
+            waker.wake();
+        }
+
+        Poll::Ready(Ok(written))
+    }
+}
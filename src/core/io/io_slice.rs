@@ -179,3 +179,51 @@ impl<'a> DerefMut for IoSliceMut<'a> {
         self.as_mut_slice()
     }
 }
+
+/// Reinterprets our slices as `std::io::IoSlice`s.
+#[cfg(feature = "std")]
+pub(crate) fn as_std_io_slices<'a, 'b>(bufs: &'b [IoSlice<'a>]) -> &'b [alloc::io::IoSlice<'a>] {
+    // Safety: both are `#[repr(transparent)]` wrappers around `libc::iovec` on unix.
+    unsafe { slice::from_raw_parts(bufs.as_ptr() as *const alloc::io::IoSlice<'a>, bufs.len()) }
+}
+
+/// Reinterprets `std::io::IoSlice`s as our slices.
+#[cfg(feature = "std")]
+pub(crate) fn from_std_io_slices<'a, 'b>(bufs: &'b [alloc::io::IoSlice<'a>]) -> &'b [IoSlice<'a>] {
+    // Safety: both are `#[repr(transparent)]` wrappers around `libc::iovec` on unix.
+    unsafe { slice::from_raw_parts(bufs.as_ptr() as *const IoSlice<'a>, bufs.len()) }
+}
+
+/// Reinterprets our slices as mutable `std::io::IoSlice`s, e.g. to fill them from a `Buf`.
+#[cfg(feature = "std")]
+pub(crate) fn as_mut_std_io_slices<'a, 'b>(
+    bufs: &'b mut [IoSlice<'a>],
+) -> &'b mut [alloc::io::IoSlice<'a>] {
+    // Safety: both are `#[repr(transparent)]` wrappers around `libc::iovec` on unix.
+    unsafe {
+        slice::from_raw_parts_mut(bufs.as_mut_ptr() as *mut alloc::io::IoSlice<'a>, bufs.len())
+    }
+}
+
+/// Reinterprets our mutable slices as `std::io::IoSliceMut`s.
+#[cfg(feature = "std")]
+pub(crate) fn as_std_io_slices_mut<'a, 'b>(
+    bufs: &'b mut [IoSliceMut<'a>],
+) -> &'b mut [alloc::io::IoSliceMut<'a>] {
+    // Safety: both are `#[repr(transparent)]` wrappers around `libc::iovec` on unix.
+    unsafe {
+        slice::from_raw_parts_mut(
+            bufs.as_mut_ptr() as *mut alloc::io::IoSliceMut<'a>,
+            bufs.len(),
+        )
+    }
+}
+
+/// Reinterprets `std::io::IoSliceMut`s as our mutable slices.
+#[cfg(feature = "std")]
+pub(crate) fn from_std_io_slices_mut<'a, 'b>(
+    bufs: &'b mut [alloc::io::IoSliceMut<'a>],
+) -> &'b mut [IoSliceMut<'a>] {
+    // Safety: both are `#[repr(transparent)]` wrappers around `libc::iovec` on unix.
+    unsafe { slice::from_raw_parts_mut(bufs.as_mut_ptr() as *mut IoSliceMut<'a>, bufs.len()) }
+}
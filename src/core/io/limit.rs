@@ -0,0 +1,123 @@
+//! Caps the number of bytes written to a stream.
+
+use crate::core::framed::IoError;
+use crate::core::io::io_slice::IoSliceMut;
+use crate::core::io::{AsyncBufRead, AsyncRead, AsyncWrite};
+use crate::Err;
+use anyhow::Result;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use pin_project_lite::pin_project;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+pin_project! {
+    /// Writes at most `limit` bytes to `inner`. Writes are truncated to the remaining limit and
+    /// fail with [`IoError::WriteLimitReached`] once it is exhausted. Reads are passed through.
+    /// Created by [`AsyncWriteExt::limit`](crate::core::io::AsyncWriteExt::limit).
+    #[derive(Debug)]
+    pub struct Limit<W> {
+        #[pin]
+        inner: W,
+        limit: u64,
+    }
+}
+
+impl<W> Limit<W> {
+    pub fn new(inner: W, limit: u64) -> Self {
+        Self { inner, limit }
+    }
+
+    /// Returns how many bytes can still be written.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = limit
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut W> {
+        self.project().inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for Limit<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        if self.limit == 0 {
+            return Poll::Ready(Err!(IoError::WriteLimitReached));
+        }
+
+        let me = self.project();
+        let max = core::cmp::min(buf.len() as u64, *me.limit) as usize;
+        let result = me.inner.poll_write(cx, &buf[..max]);
+        if let Poll::Ready(Ok(n)) = result {
+            *me.limit -= n as u64;
+        }
+
+        result
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+}
+
+impl<W: AsyncRead> AsyncRead for Limit<W> {
+    type Error = W::Error;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_read(cx, buf)
+    }
+
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize, Self::Error>> {
+        self.project().inner.poll_read_vectored(cx, bufs)
+    }
+
+    fn is_read_vectored(&self) -> bool {
+        self.inner.is_read_vectored()
+    }
+}
+
+impl<W: AsyncBufRead> AsyncBufRead for Limit<W> {
+    fn poll_fill_buf(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<&[u8], Self::Error>> {
+        self.project().inner.poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.project().inner.consume(amt)
+    }
+}
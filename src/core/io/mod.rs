@@ -2,10 +2,63 @@ mod read_buf;
 #[cfg(not(feature = "std"))]
 pub(crate) use read_buf::ReadBuf;
 
+pub mod async_buf_read;
+pub use async_buf_read::AsyncBufRead;
+
+pub mod async_buf_read_ext;
+pub use async_buf_read_ext::AsyncBufReadExt;
+
 pub mod async_read;
 pub use async_read::AsyncRead;
 
+pub mod async_read_ext;
+pub use async_read_ext::AsyncReadExt;
+
 pub mod async_write;
 pub use async_write::AsyncWrite;
 
+pub mod async_write_ext;
+pub use async_write_ext::AsyncWriteExt;
+
+pub mod buf_reader;
+pub use buf_reader::BufReader;
+
+pub mod buf_writer;
+pub use buf_writer::BufWriter;
+
+pub mod chain;
+pub use chain::Chain;
+
+pub mod compat;
+pub use compat::{CancelSafe, FromEmbeddedIo, ToEmbeddedIo};
+#[cfg(feature = "std")]
+pub use compat::{FuturesCompat, TokioCompat};
+
+pub mod copy;
+pub use copy::{copy, copy_bidirectional};
+
+pub mod counted;
+pub use counted::Counted;
+
+pub mod duplex;
+pub use duplex::{duplex, duplex_fixed, DuplexStream};
+
 pub mod io_slice;
+
+pub mod limit;
+pub use limit::Limit;
+
+pub mod reconnect;
+pub use reconnect::{Backoff, ConnectionEvent, Connector, Reconnecting, WritePolicy};
+
+pub mod split;
+pub use split::{split, ReadHalf, WriteHalf};
+
+pub mod take;
+pub use take::Take;
+
+pub mod throttle;
+pub use throttle::{Rate, Throttled};
+
+pub mod timeout;
+pub use timeout::{Timeout, Timeouts};
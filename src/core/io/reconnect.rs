@@ -0,0 +1,428 @@
+//! A stream that transparently reconnects, waiting with exponential backoff and jitter between
+//! failed attempts.
+
+use crate::core::framed::IoError;
+use crate::core::io::{AsyncRead, AsyncWrite};
+use crate::Err;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use anyhow::Result;
+use core::cmp::min;
+use core::future::{poll_fn, Future};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use embassy_time::{Duration, Timer};
+use futures::ready;
+use rand::rngs::OsRng;
+use rand_core::RngCore;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+/// Establishes the connections of a [`Reconnecting`] stream. Implemented for closures
+/// returning a connect future, e.g. `|| TcpAdapterTokio::connect(addr)`.
+pub trait Connector {
+    type Stream: AsyncRead + AsyncWrite + Unpin;
+    type Future: Future<Output = Result<Self::Stream>>;
+
+    fn connect(&mut self) -> Self::Future;
+}
+
+impl<F, Fut, S> Connector for F
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<S>>,
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    type Stream = S;
+    type Future = Fut;
+
+    fn connect(&mut self) -> Fut {
+        self()
+    }
+}
+
+/// How long to wait between failed connection attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    /// The delay after the first failed attempt.
+    pub initial: Duration,
+    /// The delay never grows beyond this.
+    pub max: Duration,
+    /// The delay is multiplied by this after every failed attempt.
+    pub multiplier: u32,
+    /// Randomizes each delay between half and the full delay, so that many devices losing
+    /// connectivity at once don't reconnect in lockstep.
+    pub jitter: bool,
+    /// Gives up after this many failed attempts in a row. `None` retries forever.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(30),
+            multiplier: 2,
+            jitter: true,
+            max_attempts: None,
+        }
+    }
+}
+
+impl Backoff {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn initial(mut self, initial: Duration) -> Self {
+        self.initial = initial;
+        self
+    }
+
+    pub fn max(mut self, max: Duration) -> Self {
+        self.max = max;
+        self
+    }
+
+    pub fn multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Returns the delay after `attempt` (starting at `1`) failed attempts in a row.
+    fn delay<R: RngCore>(&self, attempt: u32, rng: &mut R) -> Duration {
+        let factor = (self.multiplier.max(1) as u64).saturating_pow(attempt.saturating_sub(1));
+        let delay = min(
+            self.initial.as_micros().saturating_mul(factor),
+            self.max.as_micros(),
+        );
+        if !self.jitter {
+            return Duration::from_micros(delay);
+        }
+
+        let half = delay / 2;
+        Duration::from_micros(half + rng.next_u64() % (delay - half + 1))
+    }
+}
+
+/// What happens to writes while the connection is being re-established.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WritePolicy {
+    /// Writes fail with [`IoError::WriteNotConnected`] while reconnecting. Before the first
+    /// connection was established they wait for it.
+    FailFast,
+    /// Up to `limit` bytes are buffered and sent once reconnected. Writes wait while the
+    /// buffer is full.
+    Buffer(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    Connected,
+    /// The connection failed or was closed by the peer.
+    Disconnected,
+    /// Connection attempt number `attempt` failed, the next one starts after `retry_in`.
+    ConnectFailed {
+        attempt: u32,
+        retry_in: Duration,
+    },
+    /// [`Backoff::max_attempts`] was reached, the stream is unusable from now on.
+    GaveUp,
+}
+
+enum State<S, F> {
+    Disconnected,
+    Connecting(Pin<Box<F>>),
+    Backoff(Timer),
+    Connected(S),
+    GaveUp,
+}
+
+/// Wraps the streams created by a [`Connector`] and reconnects whenever reading or writing
+/// fails or the peer closes the connection. Bytes in flight when a connection breaks may be
+/// lost; protocols need their own acknowledgements if that matters.
+pub struct Reconnecting<C: Connector, R = OsRng> {
+    connector: C,
+    state: State<C::Stream, C::Future>,
+    backoff: Backoff,
+    write_policy: WritePolicy,
+    attempt: u32,
+    has_connected: bool,
+    pending: Vec<u8>,
+    rng: R,
+    on_event: Option<Box<dyn FnMut(ConnectionEvent) + Send>>,
+}
+
+impl<C: Connector> Reconnecting<C> {
+    /// Creates the stream without connecting. The first read or write connects, or use
+    /// [`Reconnecting::connect`].
+    pub fn new(connector: C, backoff: Backoff, write_policy: WritePolicy) -> Self {
+        Self::with_rng(connector, backoff, write_policy, OsRng)
+    }
+}
+
+impl<C: Connector, R: RngCore> Reconnecting<C, R> {
+    /// Like [`Reconnecting::new`], with the random number generator used for the jitter.
+    pub fn with_rng(connector: C, backoff: Backoff, write_policy: WritePolicy, rng: R) -> Self {
+        Self {
+            connector,
+            state: State::Disconnected,
+            backoff,
+            write_policy,
+            attempt: 0,
+            has_connected: false,
+            pending: Vec::new(),
+            rng,
+            on_event: None,
+        }
+    }
+
+    /// Calls `on_event` whenever the connection state changes.
+    pub fn on_event(mut self, on_event: impl FnMut(ConnectionEvent) + Send + 'static) -> Self {
+        self.on_event = Some(Box::new(on_event));
+        self
+    }
+
+    /// Waits until connected, retrying according to the backoff.
+    pub async fn connect(&mut self) -> Result<()> {
+        poll_fn(|cx| self.poll_connected(cx)).await
+    }
+
+    pub fn is_connected(&self) -> bool {
+        matches!(self.state, State::Connected(_))
+    }
+
+    /// Returns the current connection, if any.
+    pub fn get_ref(&self) -> Option<&C::Stream> {
+        match &self.state {
+            State::Connected(stream) => Some(stream),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self) -> Option<&mut C::Stream> {
+        match &mut self.state {
+            State::Connected(stream) => Some(stream),
+            _ => None,
+        }
+    }
+
+    pub fn write_policy(&self) -> WritePolicy {
+        self.write_policy
+    }
+
+    pub fn set_write_policy(&mut self, write_policy: WritePolicy) {
+        self.write_policy = write_policy;
+    }
+
+    fn emit(&mut self, event: ConnectionEvent) {
+        if let Some(on_event) = self.on_event.as_mut() {
+            on_event(event)
+        }
+    }
+
+    fn disconnect(&mut self) {
+        self.state = State::Disconnected;
+        self.emit(ConnectionEvent::Disconnected);
+    }
+
+    fn stream(&mut self) -> &mut C::Stream {
+        match &mut self.state {
+            State::Connected(stream) => stream,
+            _ => unreachable!("`stream` is only used while connected"),
+        }
+    }
+
+    /// Drives the state machine until connected.
+    fn poll_connected(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            match &mut self.state {
+                State::Connected(_) => return Poll::Ready(Ok(())),
+                State::GaveUp => return Poll::Ready(Err!(IoError::ReconnectGaveUp)),
+                State::Disconnected => {
+                    self.state = State::Connecting(Box::pin(self.connector.connect()));
+                }
+                State::Connecting(connect) => match ready!(connect.as_mut().poll(cx)) {
+                    Ok(stream) => {
+                        self.attempt = 0;
+                        self.has_connected = true;
+                        self.state = State::Connected(stream);
+                        self.emit(ConnectionEvent::Connected);
+                    }
+                    Err(_) => {
+                        self.attempt += 1;
+                        if matches!(self.backoff.max_attempts, Some(max) if self.attempt >= max) {
+                            self.state = State::GaveUp;
+                            self.emit(ConnectionEvent::GaveUp);
+                            continue;
+                        }
+                        let retry_in = self.backoff.delay(self.attempt, &mut self.rng);
+                        self.state = State::Backoff(Timer::after(retry_in));
+                        self.emit(ConnectionEvent::ConnectFailed {
+                            attempt: self.attempt,
+                            retry_in,
+                        });
+                    }
+                },
+                State::Backoff(timer) => {
+                    ready!(Pin::new(timer).poll(cx));
+                    self.state = State::Disconnected;
+                }
+            }
+        }
+    }
+
+    /// Writes the bytes buffered during a reconnect. Returns `Ok(false)` if the connection
+    /// broke meanwhile.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<bool> {
+        while !self.pending.is_empty() {
+            let pending = core::mem::take(&mut self.pending);
+            let result = Pin::new(self.stream()).poll_write(cx, &pending);
+            self.pending = pending;
+            match ready!(result) {
+                Ok(n) if n > 0 => {
+                    self.pending.drain(..n);
+                }
+                _ => {
+                    self.disconnect();
+                    return Poll::Ready(false);
+                }
+            }
+        }
+
+        Poll::Ready(true)
+    }
+}
+
+impl<C, R> AsyncRead for Reconnecting<C, R>
+where
+    C: Connector + Unpin,
+    R: RngCore + Unpin,
+{
+    type Error = anyhow::Error;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        loop {
+            ready!(this.poll_connected(cx))?;
+
+            let filled = buf.filled().len();
+            match ready!(Pin::new(this.stream()).poll_read(cx, buf)) {
+                // EOF, the peer closed the connection.
+                Ok(()) if buf.filled().len() == filled && buf.remaining() > 0 => this.disconnect(),
+                Ok(()) => return Poll::Ready(Ok(())),
+                Err(_) => this.disconnect(),
+            }
+        }
+    }
+}
+
+impl<C, R> AsyncWrite for Reconnecting<C, R>
+where
+    C: Connector + Unpin,
+    R: RngCore + Unpin,
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            match this.poll_connected(cx) {
+                Poll::Ready(Ok(())) => {
+                    if !ready!(this.poll_drain(cx)) {
+                        continue;
+                    }
+                    match ready!(Pin::new(this.stream()).poll_write(cx, buf)) {
+                        Ok(n) => return Poll::Ready(Ok(n)),
+                        Err(error) => {
+                            this.disconnect();
+                            if this.write_policy == WritePolicy::FailFast {
+                                return Poll::Ready(Err(error));
+                            }
+                        }
+                    }
+                }
+                Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+                Poll::Pending => {
+                    return match this.write_policy {
+                        // Woken once connected.
+                        WritePolicy::FailFast if !this.has_connected => Poll::Pending,
+                        WritePolicy::FailFast => Poll::Ready(Err!(IoError::WriteNotConnected)),
+                        WritePolicy::Buffer(limit) => {
+                            let n = min(buf.len(), limit.saturating_sub(this.pending.len()));
+                            if n == 0 && !buf.is_empty() {
+                                // Woken once connected.
+                                return Poll::Pending;
+                            }
+                            this.pending.extend_from_slice(&buf[..n]);
+                            Poll::Ready(Ok(n))
+                        }
+                    };
+                }
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        loop {
+            match this.poll_connected(cx) {
+                Poll::Ready(Ok(())) => {
+                    if !ready!(this.poll_drain(cx)) {
+                        continue;
+                    }
+                    match ready!(Pin::new(this.stream()).poll_flush(cx)) {
+                        Ok(()) => return Poll::Ready(Ok(())),
+                        Err(error) => {
+                            this.disconnect();
+                            if this.write_policy == WritePolicy::FailFast {
+                                return Poll::Ready(Err(error));
+                            }
+                        }
+                    }
+                }
+                Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+                Poll::Pending => {
+                    return match this.write_policy {
+                        WritePolicy::FailFast if !this.has_connected => Poll::Pending,
+                        WritePolicy::FailFast => Poll::Ready(Err!(IoError::FlushNotConnected)),
+                        // Buffered bytes are flushed once reconnected.
+                        WritePolicy::Buffer(_) => Poll::Pending,
+                    };
+                }
+            }
+        }
+    }
+
+    /// Delivers the bytes buffered during a reconnect before shutting down, as they were
+    /// already reported as written.
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if !this.is_connected() && this.pending.is_empty() {
+                return Poll::Ready(Ok(()));
+            }
+            ready!(this.poll_connected(cx))?;
+            if !ready!(this.poll_drain(cx)) {
+                continue;
+            }
+
+            return Pin::new(this.stream()).poll_shutdown(cx);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! A no_std version of `tokio::io::split`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio/src/io/split.rs>`
+//!
+//! The halves share the stream behind a lock. Streams which can be split natively should
+//! prefer that: `TcpAdapterTokio::into_split` and `TcpAdapterEmbassy::split` don't need a lock.
+
+use crate::core::io::io_slice::IoSlice;
+use crate::core::io::{AsyncRead, AsyncWrite};
+use crate::utils::lock::{Lock, LockGuard};
+use alloc::sync::Arc;
+use anyhow::Result;
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+/// Splits `stream` into a reading and a writing half, which can be used from different tasks.
+pub fn split<T: AsyncRead + AsyncWrite>(stream: T) -> (ReadHalf<T>, WriteHalf<T>) {
+    let inner = Arc::new(Lock::new(stream));
+
+    (
+        ReadHalf {
+            inner: inner.clone(),
+        },
+        WriteHalf { inner },
+    )
+}
+
+pub struct ReadHalf<T> {
+    inner: Arc<Lock<T>>,
+}
+
+pub struct WriteHalf<T> {
+    inner: Arc<Lock<T>>,
+}
+
+fn poll_lock<'a, T>(lock: &'a Lock<T>, cx: &mut Context<'_>) -> Poll<LockGuard<'a, T>> {
+    match lock.try_lock() {
+        Some(guard) => Poll::Ready(guard),
+        None => {
+            // The other half holds the lock only for a single poll, so try again right away.
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+fn stream_pin<'a, T>(guard: &'a mut LockGuard<'_, T>) -> Pin<&'a mut T> {
+    // Safety: the stream is pinned inside the `Arc` and never moved until `unsplit`,
+    // which requires `T: Unpin`. The lock guarantees exclusive access.
+    unsafe { Pin::new_unchecked(&mut **guard) }
+}
+
+impl<T> ReadHalf<T> {
+    /// Returns `true` if `other` was split from the same stream.
+    pub fn is_pair_of(&self, other: &WriteHalf<T>) -> bool {
+        other.is_pair_of(self)
+    }
+
+    /// Reunites both halves into the original stream.
+    ///
+    /// # Panics
+    ///
+    /// If `write_half` was not split from the same stream.
+    pub fn unsplit(self, write_half: WriteHalf<T>) -> T
+    where
+        T: Unpin,
+    {
+        if self.is_pair_of(&write_half) {
+            drop(write_half);
+            let inner = Arc::try_unwrap(self.inner)
+                .ok()
+                .expect("`Arc::try_unwrap` failed");
+            inner.into_inner()
+        } else {
+            panic!("Unrelated `WriteHalf` passed to `ReadHalf::unsplit`.")
+        }
+    }
+}
+
+impl<T> WriteHalf<T> {
+    /// Returns `true` if `other` was split from the same stream.
+    pub fn is_pair_of(&self, other: &ReadHalf<T>) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T: AsyncRead> AsyncRead for ReadHalf<T> {
+    type Error = T::Error;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        let mut guard = ready!(poll_lock(&self.inner, cx));
+        stream_pin(&mut guard).poll_read(cx, buf)
+    }
+}
+
+impl<T: AsyncWrite> AsyncWrite for WriteHalf<T> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        let mut guard = ready!(poll_lock(&self.inner, cx));
+        stream_pin(&mut guard).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<Result<usize>> {
+        let mut guard = ready!(poll_lock(&self.inner, cx));
+        stream_pin(&mut guard).poll_write_vectored(cx, bufs)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let mut guard = ready!(poll_lock(&self.inner, cx));
+        stream_pin(&mut guard).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let mut guard = ready!(poll_lock(&self.inner, cx));
+        stream_pin(&mut guard).poll_shutdown(cx)
+    }
+}
+
+impl<T> fmt::Debug for ReadHalf<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("split::ReadHalf").finish()
+    }
+}
+
+impl<T> fmt::Debug for WriteHalf<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("split::WriteHalf").finish()
+    }
+}
@@ -0,0 +1,139 @@
+//! A no_std version of `tokio::io::Take`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio/src/io/util/take.rs>`
+
+use crate::core::io::io_slice::IoSlice;
+use crate::core::io::{AsyncBufRead, AsyncRead, AsyncWrite};
+use anyhow::Result;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use pin_project_lite::pin_project;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+pin_project! {
+    /// Reads at most `limit` bytes from `inner`, then signals EOF. Writes are passed through.
+    /// Created by [`AsyncReadExt::take`](crate::core::io::AsyncReadExt::take).
+    #[derive(Debug)]
+    pub struct Take<R> {
+        #[pin]
+        inner: R,
+        limit: u64,
+    }
+}
+
+impl<R> Take<R> {
+    pub fn new(inner: R, limit: u64) -> Self {
+        Self { inner, limit }
+    }
+
+    /// Returns how many bytes can still be read before EOF.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = limit
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut R> {
+        self.project().inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for Take<R> {
+    type Error = R::Error;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        if self.limit == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        let me = self.project();
+        let mut b = buf.take(usize::try_from(*me.limit).unwrap_or(usize::MAX));
+        let ptr = b.filled().as_ptr();
+        ready!(me.inner.poll_read(cx, &mut b))?;
+        // Ensure the pointer does not change from under us
+        assert_eq!(ptr, b.filled().as_ptr());
+
+        let n = b.filled().len();
+        // Safety: `n` bytes were initialized and filled by the inner reader.
+        unsafe {
+            buf.assume_init(n);
+        }
+        buf.advance(n);
+        *me.limit -= n as u64;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R: AsyncBufRead> AsyncBufRead for Take<R> {
+    fn poll_fill_buf(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<&[u8], Self::Error>> {
+        if self.limit == 0 {
+            return Poll::Ready(Ok(&[]));
+        }
+
+        let me = self.project();
+        let buf = ready!(me.inner.poll_fill_buf(cx))?;
+        let cap = core::cmp::min(buf.len() as u64, *me.limit) as usize;
+
+        Poll::Ready(Ok(&buf[..cap]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let me = self.project();
+        let amt = core::cmp::min(amt as u64, *me.limit) as usize;
+        *me.limit -= amt as u64;
+        me.inner.consume(amt);
+    }
+}
+
+impl<R: AsyncWrite> AsyncWrite for Take<R> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        self.project().inner.poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<Result<usize>> {
+        self.project().inner.poll_write_vectored(cx, bufs)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
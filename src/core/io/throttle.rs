@@ -0,0 +1,252 @@
+//! Bandwidth shaping for [`AsyncRead`]/[`AsyncWrite`] streams.
+//!
+//! Reads and writes each draw from their own token bucket, which is refilled over time using
+//! `embassy_time`. A pending read or write sleeps until enough tokens are available.
+
+use crate::core::io::{AsyncRead, AsyncWrite};
+use anyhow::Result;
+use core::cmp::min;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use embassy_time::{Duration, Instant, Timer};
+use futures::ready;
+use pin_project_lite::pin_project;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+const MICROS_PER_SECOND: u128 = 1_000_000;
+
+/// The throughput allowed in one direction of a [`Throttled`] stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rate {
+    /// How many bytes are allowed per second on average. `0` pauses the direction.
+    pub bytes_per_second: u64,
+    /// How many bytes may be transferred at once after the stream has been idle.
+    pub burst: u64,
+}
+
+impl Rate {
+    /// Allows `bytes_per_second` with a burst of one second worth of bytes.
+    pub fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second,
+            burst: bytes_per_second,
+        }
+    }
+
+    pub fn burst(mut self, burst: u64) -> Self {
+        self.burst = burst;
+        self
+    }
+}
+
+struct TokenBucket {
+    rate: Option<Rate>,
+    tokens: u64,
+    last_refill: Instant,
+    timer: Option<Timer>,
+    waker: Option<Waker>,
+}
+
+impl TokenBucket {
+    fn new(rate: Option<Rate>) -> Self {
+        Self {
+            tokens: rate.map_or(0, |rate| rate.burst),
+            rate,
+            last_refill: Instant::now(),
+            timer: None,
+            waker: None,
+        }
+    }
+
+    fn set_rate(&mut self, rate: Option<Rate>) {
+        self.refill();
+        if let Some(rate) = rate {
+            self.tokens = min(self.tokens, rate.burst);
+        }
+        self.rate = rate;
+        self.timer = None;
+        // A pending read or write waits for a token computed from the old rate.
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let rate = match self.rate {
+            Some(rate) if rate.bytes_per_second > 0 => rate,
+            _ => {
+                self.last_refill = now;
+                return;
+            }
+        };
+
+        let elapsed = now.duration_since(self.last_refill).as_micros() as u128;
+        let new_tokens = elapsed * rate.bytes_per_second as u128 / MICROS_PER_SECOND;
+        if new_tokens == 0 {
+            return;
+        }
+
+        let tokens = min(self.tokens as u128 + new_tokens, rate.burst as u128) as u64;
+        if tokens == rate.burst {
+            self.last_refill = now;
+        } else {
+            // Only account for the time the new tokens took, so fractions aren't lost.
+            let used = new_tokens * MICROS_PER_SECOND / rate.bytes_per_second as u128;
+            self.last_refill = self.last_refill + Duration::from_micros(used as u64);
+        }
+        self.tokens = tokens;
+    }
+
+    /// Returns how many of `max` bytes may be transferred now, waiting for at least one token.
+    fn poll_acquire(&mut self, cx: &mut Context<'_>, max: usize) -> Poll<usize> {
+        let rate = match self.rate {
+            None => return Poll::Ready(max),
+            Some(rate) => rate,
+        };
+
+        loop {
+            self.refill();
+            if self.tokens > 0 {
+                self.timer = None;
+                return Poll::Ready(min(self.tokens, max as u64) as usize);
+            }
+
+            self.waker = Some(cx.waker().clone());
+            if rate.bytes_per_second == 0 || rate.burst == 0 {
+                // Paused until the rate is changed.
+                return Poll::Pending;
+            }
+
+            let bytes_per_second = rate.bytes_per_second as u128;
+            let micros_per_token = (MICROS_PER_SECOND + bytes_per_second - 1) / bytes_per_second;
+            let last_refill = self.last_refill;
+            let timer = self.timer.get_or_insert_with(|| {
+                Timer::at(last_refill + Duration::from_micros(micros_per_token as u64))
+            });
+            ready!(Pin::new(timer).poll(cx));
+            self.timer = None;
+        }
+    }
+
+    fn consume(&mut self, n: usize) {
+        if self.rate.is_some() {
+            self.tokens = self.tokens.saturating_sub(n as u64);
+        }
+    }
+}
+
+pin_project! {
+    /// Wraps a stream and limits its read and write throughput independently. A direction
+    /// without a [`Rate`] is not limited.
+    pub struct Throttled<T> {
+        #[pin]
+        inner: T,
+        read: TokenBucket,
+        write: TokenBucket,
+    }
+}
+
+impl<T> Throttled<T> {
+    pub fn new(inner: T, read_rate: Option<Rate>, write_rate: Option<Rate>) -> Self {
+        Self {
+            inner,
+            read: TokenBucket::new(read_rate),
+            write: TokenBucket::new(write_rate),
+        }
+    }
+
+    pub fn read_rate(&self) -> Option<Rate> {
+        self.read.rate
+    }
+
+    pub fn write_rate(&self) -> Option<Rate> {
+        self.write.rate
+    }
+
+    /// Changes the read rate. Takes effect for the pending read, if any.
+    pub fn set_read_rate(&mut self, rate: Option<Rate>) {
+        self.read.set_rate(rate)
+    }
+
+    /// Changes the write rate. Takes effect for the pending write, if any.
+    pub fn set_write_rate(&mut self, rate: Option<Rate>) {
+        self.write.set_rate(rate)
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut T> {
+        self.project().inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: AsyncRead> AsyncRead for Throttled<T> {
+    type Error = T::Error;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        let me = self.project();
+        if buf.remaining() == 0 {
+            return me.inner.poll_read(cx, buf);
+        }
+
+        let allowed = ready!(me.read.poll_acquire(cx, buf.remaining()));
+        let mut b = buf.take(allowed);
+        let ptr = b.filled().as_ptr();
+        ready!(me.inner.poll_read(cx, &mut b))?;
+        // Ensure the pointer does not change from under us
+        assert_eq!(ptr, b.filled().as_ptr());
+
+        let n = b.filled().len();
+        // Safety: `n` bytes were initialized and filled by the inner reader.
+        unsafe {
+            buf.assume_init(n);
+        }
+        buf.advance(n);
+        me.read.consume(n);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T: AsyncWrite> AsyncWrite for Throttled<T> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        let me = self.project();
+        if buf.is_empty() {
+            return me.inner.poll_write(cx, buf);
+        }
+
+        let allowed = ready!(me.write.poll_acquire(cx, buf.len()));
+        let n = ready!(me.inner.poll_write(cx, &buf[..allowed]))?;
+        me.write.consume(n);
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+}
@@ -0,0 +1,292 @@
+//! Connect, read, write and idle deadlines for [`AsyncRead`]/[`AsyncWrite`] streams.
+//!
+//! Deadlines are driven by `embassy_time`, which also works with `std` through its
+//! `generic-queue` feature.
+
+use crate::core::framed::IoError;
+use crate::core::io::{io_slice::IoSlice, AsyncRead, AsyncWrite};
+use crate::Err;
+use anyhow::Result;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use embassy_time::{with_timeout, Instant, Timer};
+use pin_project_lite::pin_project;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+// exports
+pub use embassy_time::Duration;
+
+/// Awaits `future` for at most `duration`.
+pub async fn timeout<F: Future>(duration: Duration, future: F) -> Result<F::Output> {
+    match with_timeout(duration, future).await {
+        Ok(output) => Ok(output),
+        Err(_) => Err!(IoError::TimedOut),
+    }
+}
+
+/// Awaits `connect` for at most `duration`, failing with [`IoError::ConnectTimedOut`].
+pub async fn connect_timeout<T, F>(duration: Duration, connect: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    match with_timeout(duration, connect).await {
+        Ok(result) => result,
+        Err(_) => Err!(IoError::ConnectTimedOut),
+    }
+}
+
+/// The deadlines applied by [`Timeout`]. A deadline set to `None` never expires.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Maximum time to establish the connection.
+    pub connect: Option<Duration>,
+    /// Maximum time a single read may stay pending.
+    pub read: Option<Duration>,
+    /// Maximum time a single write, flush or shutdown may stay pending.
+    pub write: Option<Duration>,
+    /// Maximum time without any successful read or write.
+    pub idle: Option<Duration>,
+}
+
+impl Timeouts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn connect(mut self, duration: Duration) -> Self {
+        self.connect = Some(duration);
+        self
+    }
+
+    pub fn read(mut self, duration: Duration) -> Self {
+        self.read = Some(duration);
+        self
+    }
+
+    pub fn write(mut self, duration: Duration) -> Self {
+        self.write = Some(duration);
+        self
+    }
+
+    pub fn idle(mut self, duration: Duration) -> Self {
+        self.idle = Some(duration);
+        self
+    }
+}
+
+pin_project! {
+    /// Wraps a stream and fails pending reads and writes once their deadline expired.
+    pub struct Timeout<T> {
+        #[pin]
+        inner: T,
+        timeouts: Timeouts,
+        read_timer: Option<Timer>,
+        write_timer: Option<Timer>,
+        idle_timer: Option<Timer>,
+        last_activity: Instant,
+    }
+}
+
+impl<T> Timeout<T> {
+    pub fn new(inner: T, timeouts: Timeouts) -> Self {
+        Self {
+            inner,
+            timeouts,
+            read_timer: None,
+            write_timer: None,
+            idle_timer: None,
+            last_activity: Instant::now(),
+        }
+    }
+
+    /// Awaits `connect` within the `connect` deadline and wraps the established stream.
+    pub async fn connect<F>(connect: F, timeouts: Timeouts) -> Result<Self>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        let inner = match timeouts.connect {
+            None => connect.await?,
+            Some(duration) => connect_timeout(duration, connect).await?,
+        };
+
+        Ok(Self::new(inner, timeouts))
+    }
+
+    pub fn timeouts(&self) -> &Timeouts {
+        &self.timeouts
+    }
+
+    pub fn set_timeouts(&mut self, timeouts: Timeouts) {
+        self.timeouts = timeouts;
+        self.read_timer = None;
+        self.write_timer = None;
+        self.idle_timer = None;
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut T> {
+        self.project().inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+/// Returns `true` if the deadline started by the first pending poll has expired.
+fn poll_deadline(
+    timer: &mut Option<Timer>,
+    duration: Option<Duration>,
+    cx: &mut Context<'_>,
+) -> bool {
+    let duration = match duration {
+        None => return false,
+        Some(duration) => duration,
+    };
+    let timer = timer.get_or_insert_with(|| Timer::after(duration));
+
+    Pin::new(timer).poll(cx).is_ready()
+}
+
+/// Returns `true` if there was no activity for longer than the `idle` deadline.
+fn poll_idle(
+    timer: &mut Option<Timer>,
+    duration: Option<Duration>,
+    last_activity: Instant,
+    cx: &mut Context<'_>,
+) -> bool {
+    let duration = match duration {
+        None => return false,
+        Some(duration) => duration,
+    };
+    let timer = timer.get_or_insert_with(|| Timer::at(last_activity + duration));
+
+    Pin::new(timer).poll(cx).is_ready()
+}
+
+macro_rules! poll_with_deadline {
+    ($this:ident, $cx:ident, $timer:ident, $duration:expr, $timed_out:expr, $poll:expr) => {
+        match $poll {
+            Poll::Ready(result) => {
+                *$this.$timer = None;
+                *$this.idle_timer = None;
+                *$this.last_activity = Instant::now();
+                match result {
+                    Ok(value) => Poll::Ready(Ok(value)),
+                    Err(error) => Poll::Ready(Err!(error)),
+                }
+            }
+            Poll::Pending => {
+                if poll_deadline(&mut *$this.$timer, $duration, $cx) {
+                    *$this.$timer = None;
+                    Poll::Ready(Err!($timed_out))
+                } else if poll_idle(
+                    &mut *$this.idle_timer,
+                    $this.timeouts.idle,
+                    *$this.last_activity,
+                    $cx,
+                ) {
+                    *$this.idle_timer = None;
+                    Poll::Ready(Err!(IoError::IdleTimedOut))
+                } else {
+                    Poll::Pending
+                }
+            }
+        }
+    };
+}
+
+impl<T: AsyncRead> AsyncRead for Timeout<T> {
+    type Error = anyhow::Error;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        let this = self.project();
+        let read = this.timeouts.read;
+        poll_with_deadline!(
+            this,
+            cx,
+            read_timer,
+            read,
+            IoError::ReadTimedOut,
+            this.inner.poll_read(cx, buf)
+        )
+    }
+}
+
+impl<T: AsyncWrite> AsyncWrite for Timeout<T> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        let this = self.project();
+        let write = this.timeouts.write;
+        poll_with_deadline!(
+            this,
+            cx,
+            write_timer,
+            write,
+            IoError::WriteTimedOut,
+            this.inner.poll_write(cx, buf)
+        )
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<Result<usize>> {
+        let this = self.project();
+        let write = this.timeouts.write;
+        poll_with_deadline!(
+            this,
+            cx,
+            write_timer,
+            write,
+            IoError::WriteTimedOut,
+            this.inner.poll_write_vectored(cx, bufs)
+        )
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.project();
+        let write = this.timeouts.write;
+        poll_with_deadline!(
+            this,
+            cx,
+            write_timer,
+            write,
+            IoError::WriteTimedOut,
+            this.inner.poll_flush(cx)
+        )
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.project();
+        let write = this.timeouts.write;
+        poll_with_deadline!(
+            this,
+            cx,
+            write_timer,
+            write,
+            IoError::WriteTimedOut,
+            this.inner.poll_shutdown(cx)
+        )
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
@@ -2,7 +2,12 @@
 pub mod dns;
 pub mod framed;
 pub mod io;
+pub mod pool;
+pub mod proxy;
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub mod serial;
 pub mod tcp;
+pub mod udp;
 // TODO: uncomment and make tls public as soon as it's working
 // #[cfg(feature = "tls")]
 // mod tls;
@@ -0,0 +1,12 @@
+use thiserror_no_std::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PoolError {
+    #[error("Too many connections to this host are in use")]
+    HostLimitReached,
+    #[error("All connections of the pool are in use")]
+    PoolFull,
+}
+
+#[cfg(feature = "std")]
+impl alloc::error::Error for PoolError {}
@@ -0,0 +1,609 @@
+//! Keeps idle connections around, so that requests to the same host reuse them instead of
+//! connecting every time.
+//!
+//! [`Pool`] grows on the heap and can be shared between tasks, [`FixedPool`] holds at most `N`
+//! connections in `heapless` storage for stacks with a fixed number of sockets.
+
+pub mod errors;
+
+pub use errors::PoolError;
+
+use crate::core::io::io_slice::{IoSlice, IoSliceMut};
+use crate::core::io::{AsyncRead, AsyncWrite};
+use crate::utils::lock::Lock;
+use crate::Err;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use anyhow::Result;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use embassy_time::{Duration, Instant};
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+/// Checks whether an idle connection can be reused.
+pub trait HealthCheck {
+    /// Returns `false` if the connection can't be reused, e.g. because the peer closed it.
+    fn is_healthy(&mut self) -> bool;
+}
+
+/// Identifies the connections that are interchangeable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PoolKey<H = String> {
+    pub host: H,
+    pub port: u16,
+    /// Identifies the TLS config of TLS connections, `None` for plain TCP. Connections are
+    /// only reused with the same config.
+    pub tls_config: Option<u32>,
+}
+
+impl<H> PoolKey<H> {
+    pub fn tcp(host: H, port: u16) -> Self {
+        Self {
+            host,
+            port,
+            tls_config: None,
+        }
+    }
+
+    pub fn tls(host: H, port: u16, tls_config: u32) -> Self {
+        Self {
+            host,
+            port,
+            tls_config: Some(tls_config),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolConfig {
+    /// Idle connections kept over all hosts. The longest idle connection is closed first.
+    pub max_idle: usize,
+    /// Connections per host handed out at the same time. [`Pool::get`] doesn't wait for a
+    /// connection to be returned, it fails right away with [`PoolError::HostLimitReached`].
+    pub max_per_host: usize,
+    /// Idle connections are closed after this long. `None` keeps them forever.
+    pub idle_timeout: Option<Duration>,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_idle: 16,
+            max_per_host: 4,
+            idle_timeout: Some(Duration::from_secs(90)),
+        }
+    }
+}
+
+impl PoolConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_idle(mut self, max_idle: usize) -> Self {
+        self.max_idle = max_idle;
+        self
+    }
+
+    pub fn max_per_host(mut self, max_per_host: usize) -> Self {
+        self.max_per_host = max_per_host;
+        self
+    }
+
+    pub fn idle_timeout(mut self, idle_timeout: Option<Duration>) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+}
+
+/// A connection checked out of a pool. Goes back to the pool when dropped.
+pub struct PooledConnection<'a, K, T> {
+    key: K,
+    connection: Option<T>,
+    owner: Owner<'a, K, T>,
+}
+
+impl<'a, K, T> PooledConnection<'a, K, T> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn get_ref(&self) -> &T {
+        self.connection
+            .as_ref()
+            .expect("connection is only taken on drop")
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.connection
+            .as_mut()
+            .expect("connection is only taken on drop")
+    }
+
+    /// Takes the connection out of the pool for good, e.g. after a protocol error or an
+    /// upgrade to another protocol.
+    pub fn detach(mut self) -> T {
+        self.connection
+            .take()
+            .expect("connection is only taken on drop")
+    }
+}
+
+// The connection is never pinned in place, only `T: Unpin` connections are read and written.
+impl<K, T> Unpin for PooledConnection<'_, K, T> {}
+
+impl<K, T> Drop for PooledConnection<'_, K, T> {
+    fn drop(&mut self) {
+        self.owner
+            .handle()
+            .release(&self.key, self.connection.take())
+    }
+}
+
+impl<K, T: AsyncRead + Unpin> AsyncRead for PooledConnection<'_, K, T> {
+    type Error = T::Error;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(self.get_mut().get_mut()).poll_read(cx, buf)
+    }
+
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize, Self::Error>> {
+        Pin::new(self.get_mut().get_mut()).poll_read_vectored(cx, bufs)
+    }
+
+    fn is_read_vectored(&self) -> bool {
+        self.get_ref().is_read_vectored()
+    }
+}
+
+impl<K, T: AsyncWrite + Unpin> AsyncWrite for PooledConnection<'_, K, T> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        Pin::new(self.get_mut().get_mut()).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<Result<usize>> {
+        Pin::new(self.get_mut().get_mut()).poll_write_vectored(cx, bufs)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(self.get_mut().get_mut()).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(self.get_mut().get_mut()).poll_shutdown(cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.get_ref().is_write_vectored()
+    }
+}
+
+type Shared<K, T> = Inner<K, T, Vec<Idle<K, T>>, Vec<Host<K>>>;
+
+/// A pool of connections on the heap. Clones share the same connections.
+pub struct Pool<T, K = PoolKey> {
+    inner: Arc<Shared<K, T>>,
+}
+
+impl<T, K> Clone for Pool<T, K> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T, K> Pool<T, K>
+where
+    T: HealthCheck + Send + 'static,
+    K: Clone + Eq + Send + 'static,
+{
+    pub fn new(config: PoolConfig) -> Self {
+        Self {
+            inner: Arc::new(Inner::new(config, Vec::new(), Vec::new(), None)),
+        }
+    }
+
+    /// Returns a healthy idle connection for `key`, or one created by `connect`. Fails with
+    /// [`PoolError::HostLimitReached`] instead of waiting if `key` reached its limit.
+    pub async fn get<F, Fut>(&self, key: K, connect: F) -> Result<PooledConnection<'static, K, T>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        get(Owner::Shared(self.inner.clone()), key, connect).await
+    }
+
+    pub fn config(&self) -> PoolConfig {
+        self.inner.config
+    }
+
+    pub fn idle_connections(&self) -> usize {
+        self.inner.state.lock().idle.len()
+    }
+
+    /// Returns how many connections are checked out.
+    pub fn active_connections(&self) -> usize {
+        self.inner.state.lock().active()
+    }
+
+    /// Closes all idle connections.
+    pub fn clear(&self) {
+        self.inner.state.lock().idle.clear()
+    }
+}
+
+type Fixed<K, T, const N: usize> =
+    Inner<K, T, heapless::Vec<Idle<K, T>, N>, heapless::Vec<Host<K>, N>>;
+
+/// A pool of at most `N` connections, idle and checked out together, e.g. one per socket of
+/// an `embassy-net` stack. Once full, the longest idle connection is closed to make room.
+pub struct FixedPool<T, K, const N: usize> {
+    inner: Fixed<K, T, N>,
+}
+
+impl<T: HealthCheck, K: Clone + Eq, const N: usize> FixedPool<T, K, N> {
+    pub fn new(config: PoolConfig) -> Self {
+        Self {
+            inner: Inner::new(config, heapless::Vec::new(), heapless::Vec::new(), Some(N)),
+        }
+    }
+
+    /// Returns a healthy idle connection for `key`, or one created by `connect`. Fails with
+    /// [`PoolError::HostLimitReached`] instead of waiting if `key` reached its limit.
+    pub async fn get<F, Fut>(&self, key: K, connect: F) -> Result<PooledConnection<'_, K, T>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        get(Owner::Borrowed(&self.inner), key, connect).await
+    }
+
+    pub fn config(&self) -> PoolConfig {
+        self.inner.config
+    }
+
+    pub fn idle_connections(&self) -> usize {
+        self.inner.state.lock().idle.len()
+    }
+
+    /// Returns how many connections are checked out.
+    pub fn active_connections(&self) -> usize {
+        self.inner.state.lock().active()
+    }
+
+    /// Closes all idle connections.
+    pub fn clear(&self) {
+        self.inner.state.lock().idle.clear()
+    }
+}
+
+async fn get<'a, K, T, F, Fut>(
+    owner: Owner<'a, K, T>,
+    key: K,
+    connect: F,
+) -> Result<PooledConnection<'a, K, T>>
+where
+    T: HealthCheck,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    loop {
+        match owner.handle().checkout(&key) {
+            Ok(Checkout::Idle(mut connection)) => {
+                if connection.is_healthy() {
+                    return Ok(PooledConnection {
+                        key,
+                        connection: Some(connection),
+                        owner,
+                    });
+                }
+                owner.handle().release(&key, None);
+            }
+            Ok(Checkout::Connect) => break,
+            Err(error) => return Err!(error),
+        }
+    }
+
+    // Frees the slot reserved by `checkout` if connecting fails.
+    let mut pooled = PooledConnection {
+        key,
+        connection: None,
+        owner,
+    };
+    pooled.connection = Some(connect().await?);
+
+    Ok(pooled)
+}
+
+enum Owner<'a, K, T> {
+    Shared(Arc<dyn Handle<K, T> + Send + Sync + 'a>),
+    Borrowed(&'a dyn Handle<K, T>),
+}
+
+impl<'a, K, T> Owner<'a, K, T> {
+    fn handle(&self) -> &dyn Handle<K, T> {
+        match self {
+            Self::Shared(handle) => handle.as_ref(),
+            Self::Borrowed(handle) => *handle,
+        }
+    }
+}
+
+enum Checkout<T> {
+    Idle(T),
+    /// A slot was reserved for a new connection.
+    Connect,
+}
+
+trait Handle<K, T> {
+    fn checkout(&self, key: &K) -> Result<Checkout<T>, PoolError>;
+
+    /// Frees the slot of a checked out connection and keeps `connection` if given.
+    fn release(&self, key: &K, connection: Option<T>);
+}
+
+struct Idle<K, T> {
+    key: K,
+    connection: T,
+    since: Instant,
+}
+
+/// Checked out connections of a host.
+struct Host<K> {
+    key: K,
+    active: usize,
+}
+
+/// Storage of the pool's entries, growable or fixed.
+trait Slots<E> {
+    fn as_slice(&self) -> &[E];
+
+    fn push(&mut self, entry: E) -> Result<(), E>;
+
+    fn swap_remove(&mut self, index: usize) -> E;
+
+    fn clear(&mut self);
+
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+}
+
+impl<E> Slots<E> for Vec<E> {
+    fn as_slice(&self) -> &[E] {
+        self
+    }
+
+    fn push(&mut self, entry: E) -> Result<(), E> {
+        Vec::push(self, entry);
+        Ok(())
+    }
+
+    fn swap_remove(&mut self, index: usize) -> E {
+        Vec::swap_remove(self, index)
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+}
+
+impl<E, const N: usize> Slots<E> for heapless::Vec<E, N> {
+    fn as_slice(&self) -> &[E] {
+        self
+    }
+
+    fn push(&mut self, entry: E) -> Result<(), E> {
+        heapless::Vec::push(self, entry)
+    }
+
+    fn swap_remove(&mut self, index: usize) -> E {
+        heapless::Vec::swap_remove(self, index)
+    }
+
+    fn clear(&mut self) {
+        heapless::Vec::clear(self)
+    }
+}
+
+struct Inner<K, T, I, H> {
+    state: Lock<State<K, T, I, H>>,
+    config: PoolConfig,
+}
+
+impl<K, T, I, H> Inner<K, T, I, H> {
+    fn new(config: PoolConfig, idle: I, hosts: H, capacity: Option<usize>) -> Self {
+        Self {
+            state: Lock::new(State {
+                idle,
+                hosts,
+                capacity,
+                _marker: PhantomData,
+            }),
+            config,
+        }
+    }
+}
+
+impl<K, T, I, H> Handle<K, T> for Inner<K, T, I, H>
+where
+    K: Clone + Eq,
+    I: Slots<Idle<K, T>>,
+    H: Slots<Host<K>>,
+{
+    fn checkout(&self, key: &K) -> Result<Checkout<T>, PoolError> {
+        self.state
+            .lock()
+            .checkout(key, &self.config, Instant::now())
+    }
+
+    fn release(&self, key: &K, connection: Option<T>) {
+        self.state
+            .lock()
+            .release(key, connection, &self.config, Instant::now())
+    }
+}
+
+struct State<K, T, I, H> {
+    idle: I,
+    hosts: H,
+    /// Maximum of idle and checked out connections together.
+    capacity: Option<usize>,
+    _marker: PhantomData<(K, T)>,
+}
+
+impl<K, T, I, H> State<K, T, I, H>
+where
+    K: Clone + Eq,
+    I: Slots<Idle<K, T>>,
+    H: Slots<Host<K>>,
+{
+    fn active(&self) -> usize {
+        self.hosts.as_slice().iter().map(|host| host.active).sum()
+    }
+
+    fn active_for(&self, key: &K) -> usize {
+        match self.hosts.as_slice().iter().find(|host| &host.key == key) {
+            Some(host) => host.active,
+            None => 0,
+        }
+    }
+
+    fn checkout(
+        &mut self,
+        key: &K,
+        config: &PoolConfig,
+        now: Instant,
+    ) -> Result<Checkout<T>, PoolError> {
+        self.close_expired(config, now);
+        if self.active_for(key) >= config.max_per_host {
+            return Err(PoolError::HostLimitReached);
+        }
+
+        // The most recently used connection is the least likely to be closed by the peer.
+        let idle = self
+            .idle
+            .as_slice()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| &entry.key == key)
+            .max_by_key(|(_, entry)| entry.since)
+            .map(|(index, _)| index);
+        if idle.is_none() {
+            if let Some(capacity) = self.capacity {
+                if self.active() + self.idle.len() >= capacity && !self.close_oldest() {
+                    return Err(PoolError::PoolFull);
+                }
+            }
+        }
+
+        self.reserve(key)?;
+        match idle {
+            Some(index) => Ok(Checkout::Idle(self.idle.swap_remove(index).connection)),
+            None => Ok(Checkout::Connect),
+        }
+    }
+
+    fn reserve(&mut self, key: &K) -> Result<(), PoolError> {
+        let hosts = self.hosts.as_slice();
+        match hosts.iter().position(|host| &host.key == key) {
+            Some(index) => {
+                let mut host = self.hosts.swap_remove(index);
+                host.active += 1;
+                // There was room for it a moment ago.
+                let _ = self.hosts.push(host);
+                Ok(())
+            }
+            None => self
+                .hosts
+                .push(Host {
+                    key: key.clone(),
+                    active: 1,
+                })
+                .map_err(|_| PoolError::PoolFull),
+        }
+    }
+
+    fn release(&mut self, key: &K, connection: Option<T>, config: &PoolConfig, now: Instant) {
+        if let Some(index) = self
+            .hosts
+            .as_slice()
+            .iter()
+            .position(|host| &host.key == key)
+        {
+            let mut host = self.hosts.swap_remove(index);
+            host.active -= 1;
+            if host.active > 0 {
+                let _ = self.hosts.push(host);
+            }
+        }
+
+        let Some(connection) = connection else {
+            return;
+        };
+        if config.max_idle == 0 {
+            return;
+        }
+        if self.idle.len() >= config.max_idle {
+            self.close_oldest();
+        }
+        // Dropped if the pool is still full.
+        let _ = self.idle.push(Idle {
+            key: key.clone(),
+            connection,
+            since: now,
+        });
+    }
+
+    fn close_expired(&mut self, config: &PoolConfig, now: Instant) {
+        let Some(idle_timeout) = config.idle_timeout else {
+            return;
+        };
+        let mut index = 0;
+        while index < self.idle.len() {
+            if self.idle.as_slice()[index].since + idle_timeout <= now {
+                self.idle.swap_remove(index);
+            } else {
+                index += 1;
+            }
+        }
+    }
+
+    /// Closes the longest idle connection. Returns `false` if there is none.
+    fn close_oldest(&mut self) -> bool {
+        let oldest = self
+            .idle
+            .as_slice()
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, entry)| entry.since)
+            .map(|(index, _)| index);
+        match oldest {
+            Some(index) => {
+                self.idle.swap_remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
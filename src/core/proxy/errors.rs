@@ -0,0 +1,26 @@
+use thiserror_no_std::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ProxyError {
+    #[error("Proxy refused the tunnel with HTTP status {0}")]
+    HttpStatus(u16),
+    #[error("Proxy sent an invalid response")]
+    InvalidResponse,
+    #[error("Proxy response headers are too long")]
+    ResponseTooLong,
+    #[error("Proxy speaks SOCKS version {0} instead of 5")]
+    UnsupportedVersion(u8),
+    #[error("Proxy accepts none of the offered authentication methods")]
+    NoAcceptableAuth,
+    #[error("Proxy rejected the credentials")]
+    AuthFailed,
+    #[error("Proxy failed to connect to the target (SOCKS5 reply {0})")]
+    ConnectFailed(u8),
+    #[error("Domain names must not contain whitespace or line breaks")]
+    InvalidHost,
+    #[error("Domain names, usernames and passwords are limited to 255 bytes")]
+    TooLong,
+}
+
+#[cfg(feature = "std")]
+impl alloc::error::Error for ProxyError {}
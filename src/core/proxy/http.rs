@@ -0,0 +1,121 @@
+//! HTTP CONNECT tunnels (RFC 9110, section 9.3.6) with optional basic auth.
+
+use crate::core::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use crate::core::proxy::{ProxyError, ProxyTarget};
+use crate::Err;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use anyhow::Result;
+
+const MAX_RESPONSE_LEN: usize = 8 * 1024;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HttpConnectProxy<'a> {
+    credentials: Option<(&'a str, &'a str)>,
+}
+
+impl<'a> HttpConnectProxy<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Authenticates with `Proxy-Authorization: Basic`.
+    pub fn basic_auth(mut self, username: &'a str, password: &'a str) -> Self {
+        self.credentials = Some((username, password));
+        self
+    }
+
+    /// Asks the proxy behind `stream` to open a tunnel to `target` and returns the stream once
+    /// it did. A [`ProxyTarget::Domain`] with whitespace or line breaks, which could inject
+    /// headers into the request, fails with [`ProxyError::InvalidHost`].
+    pub async fn connect<T>(&self, mut stream: T, target: ProxyTarget<'_>) -> Result<T>
+    where
+        T: AsyncRead + AsyncWrite + Unpin,
+    {
+        if let ProxyTarget::Domain(host, _) = target {
+            if host.contains(['\r', '\n', ' ']) {
+                return Err!(ProxyError::InvalidHost);
+            }
+        }
+        let authority = target.authority();
+        let mut request = format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n", authority);
+        if let Some((username, password)) = self.credentials {
+            let credentials = format!("{}:{}", username, password);
+            request.push_str("Proxy-Authorization: Basic ");
+            request.push_str(&base64(credentials.as_bytes()));
+            request.push_str("\r\n");
+        }
+        request.push_str("\r\n");
+        stream.write_all(request.as_bytes()).await?;
+        stream.flush().await?;
+
+        let status = read_status(&mut stream).await?;
+        if !(200..300).contains(&status) {
+            return Err!(ProxyError::HttpStatus(status));
+        }
+
+        Ok(stream)
+    }
+}
+
+/// Reads the response head and returns its status code. Reads byte by byte, so that nothing
+/// the target sends right after the head is consumed.
+async fn read_status<T: AsyncRead + Unpin>(stream: &mut T) -> Result<u16> {
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() == MAX_RESPONSE_LEN {
+            return Err!(ProxyError::ResponseTooLong);
+        }
+        stream.read_exact(&mut byte).await?;
+        head.push(byte[0]);
+    }
+
+    // HTTP/1.1 200 Connection established
+    let status_line = match head.split(|byte| *byte == b'\r').next() {
+        Some(line) => line,
+        None => return Err!(ProxyError::InvalidResponse),
+    };
+    let mut parts = status_line.split(|byte| *byte == b' ');
+    if !parts
+        .next()
+        .is_some_and(|version| version.starts_with(b"HTTP/1."))
+    {
+        return Err!(ProxyError::InvalidResponse);
+    }
+    match parts
+        .next()
+        .and_then(|status| core::str::from_utf8(status).ok())
+        .and_then(|status| status.parse().ok())
+    {
+        Some(status) => Ok(status),
+        None => Err!(ProxyError::InvalidResponse),
+    }
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard base64 with padding, all basic auth needs.
+fn base64(input: &[u8]) -> String {
+    let mut output = String::with_capacity((input.len() + 2) / 3 * 4);
+    for chunk in input.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let group = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (group >> (18 - 6 * i)) & 0x3f;
+                output.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+
+    output
+}
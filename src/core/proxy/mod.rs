@@ -0,0 +1,48 @@
+//! Tunnels through HTTP CONNECT and SOCKS5 proxies.
+//!
+//! The proxies take a stream already connected to the proxy, e.g. a `TcpAdapterTokio`, and
+//! return it once the tunnel to the target is established. TLS, `Framed` or the websocket
+//! client then run on top of it like on a direct connection.
+
+pub mod errors;
+pub mod http;
+pub mod socks5;
+
+pub use errors::ProxyError;
+pub use http::HttpConnectProxy;
+pub use socks5::Socks5Proxy;
+
+use alloc::format;
+use alloc::string::String;
+use core::net::SocketAddr;
+
+/// Where the proxy should connect to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyTarget<'a> {
+    /// Resolved by the proxy (remote DNS).
+    Domain(&'a str, u16),
+    Addr(SocketAddr),
+}
+
+impl<'a> ProxyTarget<'a> {
+    pub fn port(&self) -> u16 {
+        match self {
+            Self::Domain(_, port) => *port,
+            Self::Addr(addr) => addr.port(),
+        }
+    }
+
+    /// Returns `host:port`, with IPv6 addresses in brackets.
+    pub(crate) fn authority(&self) -> String {
+        match self {
+            Self::Domain(host, port) => format!("{}:{}", host, port),
+            Self::Addr(addr) => format!("{}", addr),
+        }
+    }
+}
+
+impl From<SocketAddr> for ProxyTarget<'_> {
+    fn from(addr: SocketAddr) -> Self {
+        Self::Addr(addr)
+    }
+}
@@ -0,0 +1,141 @@
+//! SOCKS5 tunnels (RFC 1928) with optional username/password auth (RFC 1929).
+
+use crate::core::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use crate::core::proxy::{ProxyError, ProxyTarget};
+use crate::Err;
+use alloc::vec::Vec;
+use anyhow::Result;
+use core::net::SocketAddr;
+
+const VERSION: u8 = 0x05;
+const NO_AUTH: u8 = 0x00;
+const USERNAME_PASSWORD: u8 = 0x02;
+const NO_ACCEPTABLE_METHOD: u8 = 0xff;
+const AUTH_VERSION: u8 = 0x01;
+const CONNECT: u8 = 0x01;
+const IPV4: u8 = 0x01;
+const DOMAIN: u8 = 0x03;
+const IPV6: u8 = 0x04;
+const SUCCEEDED: u8 = 0x00;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Socks5Proxy<'a> {
+    credentials: Option<(&'a str, &'a str)>,
+}
+
+impl<'a> Socks5Proxy<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Offers username/password auth besides no auth.
+    pub fn auth(mut self, username: &'a str, password: &'a str) -> Self {
+        self.credentials = Some((username, password));
+        self
+    }
+
+    /// Asks the proxy behind `stream` to open a tunnel to `target` and returns the stream once
+    /// it did. [`ProxyTarget::Domain`] is resolved by the proxy.
+    pub async fn connect<T>(&self, mut stream: T, target: ProxyTarget<'_>) -> Result<T>
+    where
+        T: AsyncRead + AsyncWrite + Unpin,
+    {
+        self.authenticate(&mut stream).await?;
+
+        let mut request = Vec::from([VERSION, CONNECT, 0x00]);
+        match target {
+            ProxyTarget::Domain(host, _) => {
+                request.push(DOMAIN);
+                push_with_len(&mut request, host.as_bytes())?;
+            }
+            ProxyTarget::Addr(SocketAddr::V4(addr)) => {
+                request.push(IPV4);
+                request.extend_from_slice(&addr.ip().octets());
+            }
+            ProxyTarget::Addr(SocketAddr::V6(addr)) => {
+                request.push(IPV6);
+                request.extend_from_slice(&addr.ip().octets());
+            }
+        }
+        request.extend_from_slice(&target.port().to_be_bytes());
+        stream.write_all(&request).await?;
+        stream.flush().await?;
+
+        let mut reply = [0u8; 4];
+        stream.read_exact(&mut reply).await?;
+        if reply[0] != VERSION {
+            return Err!(ProxyError::UnsupportedVersion(reply[0]));
+        }
+        if reply[1] != SUCCEEDED {
+            return Err!(ProxyError::ConnectFailed(reply[1]));
+        }
+        // Skip the address the proxy bound for the tunnel.
+        let address_len = match reply[3] {
+            IPV4 => 4,
+            IPV6 => 16,
+            DOMAIN => {
+                let mut len = [0u8; 1];
+                stream.read_exact(&mut len).await?;
+                len[0] as usize
+            }
+            _ => return Err!(ProxyError::InvalidResponse),
+        };
+        let mut bound = [0u8; 255 + 2];
+        stream.read_exact(&mut bound[..address_len + 2]).await?;
+
+        Ok(stream)
+    }
+
+    async fn authenticate<T>(&self, stream: &mut T) -> Result<()>
+    where
+        T: AsyncRead + AsyncWrite + Unpin,
+    {
+        match self.credentials {
+            Some(_) => {
+                stream
+                    .write_all(&[VERSION, 2, NO_AUTH, USERNAME_PASSWORD])
+                    .await?
+            }
+            None => stream.write_all(&[VERSION, 1, NO_AUTH]).await?,
+        }
+        stream.flush().await?;
+
+        let mut choice = [0u8; 2];
+        stream.read_exact(&mut choice).await?;
+        if choice[0] != VERSION {
+            return Err!(ProxyError::UnsupportedVersion(choice[0]));
+        }
+        let (username, password) = match (choice[1], self.credentials) {
+            (NO_AUTH, _) => return Ok(()),
+            (USERNAME_PASSWORD, Some(credentials)) => credentials,
+            (NO_ACCEPTABLE_METHOD, _) => return Err!(ProxyError::NoAcceptableAuth),
+            _ => return Err!(ProxyError::InvalidResponse),
+        };
+
+        let mut request = Vec::from([AUTH_VERSION]);
+        push_with_len(&mut request, username.as_bytes())?;
+        push_with_len(&mut request, password.as_bytes())?;
+        stream.write_all(&request).await?;
+        stream.flush().await?;
+
+        let mut status = [0u8; 2];
+        stream.read_exact(&mut status).await?;
+        if status[1] != SUCCEEDED {
+            return Err!(ProxyError::AuthFailed);
+        }
+
+        Ok(())
+    }
+}
+
+/// Appends the length of `bytes` as a single byte, followed by `bytes`.
+fn push_with_len(buf: &mut Vec<u8>, bytes: &[u8]) -> Result<()> {
+    let len = match u8::try_from(bytes.len()) {
+        Ok(len) => len,
+        Err(_) => return Err!(ProxyError::TooLong),
+    };
+    buf.push(len);
+    buf.extend_from_slice(bytes);
+
+    Ok(())
+}
@@ -0,0 +1,321 @@
+//! Serial ports (ttys) on Linux, configured through termios and driven by tokio.
+
+use crate::core::io;
+use crate::core::serial::errors::SerialError;
+use crate::Err;
+use anyhow::Result;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use libc::{speed_t, termios};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::string::ToString;
+use tokio::io::unix::AsyncFd;
+use tokio::io::ReadBuf;
+use tokio::task::JoinHandle;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DataBits {
+    Five,
+    Six,
+    Seven,
+    #[default]
+    Eight,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    #[default]
+    None,
+    Odd,
+    Even,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StopBits {
+    #[default]
+    One,
+    Two,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FlowControl {
+    #[default]
+    None,
+    /// XON/XOFF
+    Software,
+    /// RTS/CTS
+    Hardware,
+}
+
+/// The line settings of a serial port. Defaults to 115200 baud, 8N1 without flow control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SerialConfig {
+    pub baud_rate: u32,
+    pub data_bits: DataBits,
+    pub parity: Parity,
+    pub stop_bits: StopBits,
+    pub flow_control: FlowControl,
+}
+
+impl Default for SerialConfig {
+    fn default() -> Self {
+        Self {
+            baud_rate: 115_200,
+            data_bits: DataBits::default(),
+            parity: Parity::default(),
+            stop_bits: StopBits::default(),
+            flow_control: FlowControl::default(),
+        }
+    }
+}
+
+impl SerialConfig {
+    pub fn new(baud_rate: u32) -> Self {
+        Self {
+            baud_rate,
+            ..Self::default()
+        }
+    }
+
+    pub fn data_bits(mut self, data_bits: DataBits) -> Self {
+        self.data_bits = data_bits;
+        self
+    }
+
+    pub fn parity(mut self, parity: Parity) -> Self {
+        self.parity = parity;
+        self
+    }
+
+    pub fn stop_bits(mut self, stop_bits: StopBits) -> Self {
+        self.stop_bits = stop_bits;
+        self
+    }
+
+    pub fn flow_control(mut self, flow_control: FlowControl) -> Self {
+        self.flow_control = flow_control;
+        self
+    }
+}
+
+fn to_speed(baud_rate: u32) -> Result<speed_t> {
+    let speed = match baud_rate {
+        1200 => libc::B1200,
+        2400 => libc::B2400,
+        4800 => libc::B4800,
+        9600 => libc::B9600,
+        19_200 => libc::B19200,
+        38_400 => libc::B38400,
+        57_600 => libc::B57600,
+        115_200 => libc::B115200,
+        230_400 => libc::B230400,
+        460_800 => libc::B460800,
+        500_000 => libc::B500000,
+        576_000 => libc::B576000,
+        921_600 => libc::B921600,
+        1_000_000 => libc::B1000000,
+        1_500_000 => libc::B1500000,
+        2_000_000 => libc::B2000000,
+        3_000_000 => libc::B3000000,
+        4_000_000 => libc::B4000000,
+        _ => return Err!(SerialError::UnsupportedBaudRate(baud_rate)),
+    };
+
+    Ok(speed)
+}
+
+/// Puts the tty into raw mode and applies `config`.
+fn configure(file: &File, config: &SerialConfig) -> Result<()> {
+    let speed = to_speed(config.baud_rate)?;
+    let fd = file.as_raw_fd();
+
+    // Safety: `termios` is plain data and `fd` is a valid, open file descriptor.
+    unsafe {
+        let mut termios: termios = core::mem::zeroed();
+        if libc::tcgetattr(fd, &mut termios) != 0 {
+            return Err!(SerialError::UnableToConfigure(
+                std::io::Error::last_os_error().to_string()
+            ));
+        }
+
+        libc::cfmakeraw(&mut termios);
+        termios.c_cflag |= libc::CREAD | libc::CLOCAL;
+
+        termios.c_cflag &= !libc::CSIZE;
+        termios.c_cflag |= match config.data_bits {
+            DataBits::Five => libc::CS5,
+            DataBits::Six => libc::CS6,
+            DataBits::Seven => libc::CS7,
+            DataBits::Eight => libc::CS8,
+        };
+
+        termios.c_cflag &= !(libc::PARENB | libc::PARODD);
+        termios.c_iflag &= !libc::INPCK;
+        match config.parity {
+            Parity::None => {}
+            Parity::Odd => {
+                termios.c_cflag |= libc::PARENB | libc::PARODD;
+                termios.c_iflag |= libc::INPCK;
+            }
+            Parity::Even => {
+                termios.c_cflag |= libc::PARENB;
+                termios.c_iflag |= libc::INPCK;
+            }
+        }
+
+        match config.stop_bits {
+            StopBits::One => termios.c_cflag &= !libc::CSTOPB,
+            StopBits::Two => termios.c_cflag |= libc::CSTOPB,
+        }
+
+        termios.c_cflag &= !libc::CRTSCTS;
+        termios.c_iflag &= !(libc::IXON | libc::IXOFF | libc::IXANY);
+        match config.flow_control {
+            FlowControl::None => {}
+            FlowControl::Software => termios.c_iflag |= libc::IXON | libc::IXOFF,
+            FlowControl::Hardware => termios.c_cflag |= libc::CRTSCTS,
+        }
+
+        // The file descriptor is non-blocking, reads return whatever is available.
+        termios.c_cc[libc::VMIN] = 0;
+        termios.c_cc[libc::VTIME] = 0;
+
+        if libc::cfsetispeed(&mut termios, speed) != 0
+            || libc::cfsetospeed(&mut termios, speed) != 0
+            || libc::tcsetattr(fd, libc::TCSANOW, &termios) != 0
+        {
+            return Err!(SerialError::UnableToConfigure(
+                std::io::Error::last_os_error().to_string()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// A serial port implementing [`io::AsyncRead`]/[`io::AsyncWrite`], e.g. to run `Framed`
+/// codecs over a serial modem.
+#[derive(Debug)]
+pub struct SerialAdapterTokio {
+    pub(crate) inner: AsyncFd<File>,
+    config: SerialConfig,
+    /// The `tcdrain` of a pending flush.
+    drain: Option<JoinHandle<std::io::Result<()>>>,
+}
+
+impl SerialAdapterTokio {
+    /// Opens the tty at `path` (e.g. `/dev/ttyUSB0`) and applies `config`.
+    pub fn open(path: impl AsRef<Path>, config: SerialConfig) -> Result<Self> {
+        let file = match OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(libc::O_NOCTTY | libc::O_NONBLOCK)
+            .open(path)
+        {
+            Ok(file) => file,
+            Err(error) => return Err!(SerialError::UnableToOpen(error.to_string())),
+        };
+        configure(&file, &config)?;
+
+        match AsyncFd::new(file) {
+            Ok(inner) => Ok(Self {
+                inner,
+                config,
+                drain: None,
+            }),
+            Err(error) => Err!(SerialError::UnableToOpen(error.to_string())),
+        }
+    }
+
+    pub fn config(&self) -> &SerialConfig {
+        &self.config
+    }
+
+    /// Applies a new line configuration.
+    pub fn set_config(&mut self, config: SerialConfig) -> Result<()> {
+        configure(self.inner.get_ref(), &config)?;
+        self.config = config;
+
+        Ok(())
+    }
+}
+
+impl io::AsyncRead for SerialAdapterTokio {
+    type Error = anyhow::Error;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        loop {
+            let mut guard = match ready!(self.inner.poll_read_ready(cx)) {
+                Ok(guard) => guard,
+                Err(error) => return Poll::Ready(Err!(error)),
+            };
+
+            match guard.try_io(|inner| inner.get_ref().read(buf.initialize_unfilled())) {
+                Ok(Ok(n)) => {
+                    buf.advance(n);
+                    return Poll::Ready(Ok(()));
+                }
+                Ok(Err(error)) => return Poll::Ready(Err!(error)),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+}
+
+impl io::AsyncWrite for SerialAdapterTokio {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        loop {
+            let mut guard = match ready!(self.inner.poll_write_ready(cx)) {
+                Ok(guard) => guard,
+                Err(error) => return Poll::Ready(Err!(error)),
+            };
+
+            match guard.try_io(|inner| inner.get_ref().write(buf)) {
+                Ok(Ok(n)) => return Poll::Ready(Ok(n)),
+                Ok(Err(error)) => return Poll::Ready(Err!(error)),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    /// Waits until the kernel transmitted all written bytes, e.g. before changing the baud
+    /// rate or closing the port.
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        if self.drain.is_none() {
+            // `tcdrain` blocks, so it runs on a duplicate of the fd on the blocking pool.
+            let file = match self.inner.get_ref().try_clone() {
+                Ok(file) => file,
+                Err(error) => return Poll::Ready(Err!(error)),
+            };
+            self.drain = Some(tokio::task::spawn_blocking(move || {
+                // Safety: `file` owns the duplicated fd until the closure returns.
+                match unsafe { libc::tcdrain(file.as_raw_fd()) } {
+                    0 => Ok(()),
+                    _ => Err(std::io::Error::last_os_error()),
+                }
+            }));
+        }
+
+        let result = ready!(Pin::new(self.drain.as_mut().unwrap()).poll(cx));
+        self.drain = None;
+        match result {
+            Ok(Ok(())) => Poll::Ready(Ok(())),
+            Ok(Err(error)) => Poll::Ready(Err!(error)),
+            Err(error) => Poll::Ready(Err!(error)),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_flush(cx)
+    }
+}
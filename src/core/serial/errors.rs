@@ -0,0 +1,15 @@
+use alloc::string::String;
+use thiserror_no_std::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SerialError {
+    #[error("Unable to open serial port: {0}")]
+    UnableToOpen(String),
+    #[error("Unable to configure serial port: {0}")]
+    UnableToConfigure(String),
+    #[error("Unsupported baud rate: {0}")]
+    UnsupportedBaudRate(u32),
+}
+
+#[cfg(feature = "std")]
+impl alloc::error::Error for SerialError {}
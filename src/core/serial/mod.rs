@@ -0,0 +1,4 @@
+pub mod adapters;
+pub mod errors;
+
+pub use adapters::{DataBits, FlowControl, Parity, SerialAdapterTokio, SerialConfig, StopBits};
@@ -1,27 +1,314 @@
+pub use no_std_adapters::TcpAdapterEmbassy;
 #[cfg(feature = "std")]
-pub use std_adapters::TcpAdapterTokio;
+pub use std_adapters::{TcpAdapterTokio, TcpReadHalfTokio, TcpWriteHalfTokio};
 
 #[cfg(feature = "std")]
 mod std_adapters {
     use crate::core::io;
+    use crate::core::io::io_slice::{as_std_io_slices_mut, IoSlice, IoSliceMut};
+    use crate::core::io::timeout::{self, Duration};
+    use crate::core::io::TokioCompat;
+    use crate::core::io::{AsyncRead as _, AsyncWrite as _};
+    use crate::core::pool::HealthCheck;
     use crate::core::tcp::errors::TcpError;
+    use crate::core::tcp::options::{KeepAlive, TcpOptions};
+    #[cfg(target_os = "linux")]
+    use crate::core::tcp::state::from_linux_tcp_state;
+    use crate::core::tcp::state::TcpState;
     use crate::Err;
     use anyhow::Result;
+    use core::net::SocketAddr;
     use core::pin::Pin;
     use core::task::{Context, Poll};
+    use futures::ready;
+    #[cfg(target_os = "linux")]
+    use libc::{c_void, socklen_t};
+    use socket2::{SockRef, TcpKeepalive};
+    use std::io::ErrorKind;
+    use std::net::Shutdown;
+    #[cfg(target_os = "linux")]
+    use std::os::unix::io::AsRawFd;
+    use std::string::ToString;
     use tokio::io::ReadBuf;
-    use tokio::io::{AsyncRead, AsyncWrite};
-    use tokio::net::{TcpStream, ToSocketAddrs};
+    use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+    use tokio::net::{lookup_host, TcpSocket, TcpStream, ToSocketAddrs};
+
+    /// The reading half of a [`TcpAdapterTokio`], see [`TcpAdapterTokio::into_split`].
+    pub type TcpReadHalfTokio = TokioCompat<OwnedReadHalf>;
+    /// The writing half of a [`TcpAdapterTokio`], see [`TcpAdapterTokio::into_split`].
+    pub type TcpWriteHalfTokio = TokioCompat<OwnedWriteHalf>;
+
+    fn to_std_duration(duration: Duration) -> std::time::Duration {
+        std::time::Duration::from_micros(duration.as_micros())
+    }
+
+    fn set_option(result: std::io::Result<()>) -> Result<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(error) => Err!(TcpError::UnableToSetOption(error.to_string())),
+        }
+    }
+
+    /// The keepalive interval and probe count can't be configured on every target, asking for
+    /// them fails with [`TcpError::UnsupportedOption`] there.
+    fn tcp_keepalive(keep_alive: &KeepAlive) -> Result<TcpKeepalive> {
+        let params = TcpKeepalive::new().with_time(to_std_duration(keep_alive.idle));
+
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            windows
+        ))]
+        let params = match keep_alive.interval {
+            Some(interval) => params.with_interval(to_std_duration(interval)),
+            None => params,
+        };
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            windows
+        )))]
+        if keep_alive.interval.is_some() {
+            return Err!(TcpError::UnsupportedOption("keepalive interval"));
+        }
+
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "freebsd",
+            target_os = "netbsd"
+        ))]
+        let params = match keep_alive.count {
+            Some(count) => params.with_retries(count),
+            None => params,
+        };
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "freebsd",
+            target_os = "netbsd"
+        )))]
+        if keep_alive.count.is_some() {
+            return Err!(TcpError::UnsupportedOption("keepalive count"));
+        }
+
+        Ok(params)
+    }
+
+    /// Applies `options` to `socket`, connected or not. Unsupported options are rejected
+    /// before anything is changed.
+    fn apply_options(socket: SockRef<'_>, is_ipv6: bool, options: &TcpOptions) -> Result<()> {
+        #[cfg(not(target_os = "linux"))]
+        if options.timeout.is_some() {
+            return Err!(TcpError::UnsupportedOption("timeout"));
+        }
+        let keep_alive = match options.keep_alive {
+            Some(Some(keep_alive)) => Some(Some(tcp_keepalive(&keep_alive)?)),
+            Some(None) => Some(None),
+            None => None,
+        };
+
+        match keep_alive {
+            Some(Some(keep_alive)) => set_option(socket.set_tcp_keepalive(&keep_alive))?,
+            Some(None) => set_option(socket.set_keepalive(false))?,
+            None => {}
+        }
+
+        if let Some(nodelay) = options.nodelay {
+            set_option(socket.set_nodelay(nodelay))?;
+        }
+
+        if let Some(size) = options.send_buffer_size {
+            set_option(socket.set_send_buffer_size(size))?;
+        }
+
+        if let Some(size) = options.recv_buffer_size {
+            set_option(socket.set_recv_buffer_size(size))?;
+        }
+
+        if let Some(linger) = options.linger {
+            set_option(socket.set_linger(linger.map(to_std_duration)))?;
+        }
+
+        if let Some(hop_limit) = options.hop_limit {
+            match is_ipv6 {
+                false => set_option(socket.set_ttl(hop_limit as u32))?,
+                true => set_option(socket.set_unicast_hops_v6(hop_limit as u32))?,
+            }
+        }
+
+        // `None` restores the system default.
+        #[cfg(target_os = "linux")]
+        if let Some(timeout) = options.timeout {
+            set_option(socket.set_tcp_user_timeout(timeout.map(to_std_duration)))?;
+        }
+
+        Ok(())
+    }
+
     #[derive(Debug)]
     pub struct TcpAdapterTokio {
-        pub(crate) inner: TcpStream,
+        inner: TokioCompat<TcpStream>,
+    }
+
+    impl From<TcpStream> for TcpAdapterTokio {
+        fn from(stream: TcpStream) -> Self {
+            Self {
+                inner: TokioCompat::new(stream),
+            }
+        }
     }
 
     impl TcpAdapterTokio {
         pub async fn connect(ip: impl ToSocketAddrs) -> Result<Self> {
             match TcpStream::connect(ip).await {
                 Err(_) => Err!(TcpError::UnableToConnect), // TODO: return the error returned by `tokio::net::TcpStream`
-                Ok(stream) => Ok(Self { inner: stream }),
+                Ok(stream) => Ok(Self::from(stream)),
+            }
+        }
+
+        /// Like [`TcpAdapterTokio::connect`] but fails if the connection could not be
+        /// established within `connect_timeout`.
+        pub async fn connect_with_timeout(
+            ip: impl ToSocketAddrs,
+            connect_timeout: Duration,
+        ) -> Result<Self> {
+            timeout::connect_timeout(connect_timeout, Self::connect(ip)).await
+        }
+
+        /// Like [`TcpAdapterTokio::connect`] but applies `options` to the socket before it
+        /// connects, so that the buffer sizes are taken into account by the handshake.
+        pub async fn connect_with_options(
+            ip: impl ToSocketAddrs,
+            options: &TcpOptions,
+        ) -> Result<Self> {
+            let addrs = match lookup_host(ip).await {
+                Ok(addrs) => addrs,
+                Err(_) => return Err!(TcpError::UnableToConnect),
+            };
+            for addr in addrs {
+                let socket = match addr {
+                    SocketAddr::V4(_) => TcpSocket::new_v4(),
+                    SocketAddr::V6(_) => TcpSocket::new_v6(),
+                };
+                let Ok(socket) = socket else {
+                    continue;
+                };
+                apply_options(SockRef::from(&socket), addr.is_ipv6(), options)?;
+                if let Ok(stream) = socket.connect(addr).await {
+                    return Ok(Self::from(stream));
+                }
+            }
+
+            Err!(TcpError::UnableToConnect)
+        }
+
+        /// Applies `options` to the connection. Options set to `None` are left untouched.
+        ///
+        /// The timeout is Linux specific, on other targets it fails with
+        /// [`TcpError::UnsupportedOption`]. So do the keepalive interval and count where they
+        /// can't be configured.
+        pub fn set_options(&self, options: &TcpOptions) -> Result<()> {
+            match self.get_ref().local_addr() {
+                Ok(addr) => apply_options(SockRef::from(self.get_ref()), addr.is_ipv6(), options),
+                Err(error) => Err!(TcpError::UnableToSetOption(error.to_string())),
+            }
+        }
+
+        pub fn get_ref(&self) -> &TcpStream {
+            self.inner.get_ref()
+        }
+
+        /// Closes the write side (sends FIN) while the connection can still be read from.
+        pub fn shutdown_write(&self) -> Result<()> {
+            match SockRef::from(self.get_ref()).shutdown(Shutdown::Write) {
+                Ok(()) => Ok(()),
+                Err(error) => Err!(error),
+            }
+        }
+
+        pub fn local_addr(&self) -> Result<SocketAddr> {
+            match self.get_ref().local_addr() {
+                Ok(addr) => Ok(addr),
+                Err(error) => Err!(error),
+            }
+        }
+
+        pub fn peer_addr(&self) -> Result<SocketAddr> {
+            match self.get_ref().peer_addr() {
+                Ok(addr) => Ok(addr),
+                Err(_) => Err!(TcpError::NotConnected),
+            }
+        }
+
+        /// Returns the state of the connection as reported by the kernel (`TCP_INFO`).
+        #[cfg(target_os = "linux")]
+        pub fn state(&self) -> Result<TcpState> {
+            // Safety: `tcp_info` is plain data, the kernel fills at most `len` bytes of it.
+            let (result, info) = unsafe {
+                let mut info: libc::tcp_info = core::mem::zeroed();
+                let mut len = core::mem::size_of::<libc::tcp_info>() as socklen_t;
+                let result = libc::getsockopt(
+                    self.get_ref().as_raw_fd(),
+                    libc::IPPROTO_TCP,
+                    libc::TCP_INFO,
+                    &mut info as *mut libc::tcp_info as *mut c_void,
+                    &mut len,
+                );
+                (result, info)
+            };
+            if result != 0 {
+                return Err!(std::io::Error::last_os_error());
+            }
+
+            match from_linux_tcp_state(info.tcpi_state) {
+                Some(state) => Ok(state),
+                None => Err!(TcpError::NotConnected),
+            }
+        }
+
+        /// `TCP_INFO` is Linux specific, on other targets this fails with
+        /// [`TcpError::UnsupportedOption`].
+        #[cfg(not(target_os = "linux"))]
+        pub fn state(&self) -> Result<TcpState> {
+            Err!(TcpError::UnsupportedOption("state"))
+        }
+
+        /// Splits the connection into a reading and a writing half without any locking.
+        pub fn into_split(self) -> (TcpReadHalfTokio, TcpWriteHalfTokio) {
+            let (read_half, write_half) = self.inner.into_inner().into_split();
+
+            (TokioCompat::new(read_half), TokioCompat::new(write_half))
+        }
+
+        /// Reunites the halves returned by [`TcpAdapterTokio::into_split`].
+        pub fn unsplit(read_half: TcpReadHalfTokio, write_half: TcpWriteHalfTokio) -> Result<Self> {
+            match read_half.into_inner().reunite(write_half.into_inner()) {
+                Ok(stream) => Ok(Self::from(stream)),
+                Err(_) => Err!(TcpError::UnrelatedHalves),
+            }
+        }
+    }
+
+    impl HealthCheck for TcpAdapterTokio {
+        /// The connection is healthy as long as neither side closed it. Without `TCP_INFO` it
+        /// is healthy as long as it has a peer.
+        fn is_healthy(&mut self) -> bool {
+            match self.state() {
+                Ok(state) => state == TcpState::Established,
+                Err(_) => self.peer_addr().is_ok(),
             }
         }
     }
@@ -34,14 +321,31 @@ mod std_adapters {
             cx: &mut Context<'_>,
             buf: &mut ReadBuf<'_>,
         ) -> Poll<Result<(), Self::Error>> {
-            match Pin::new(&mut self.inner).poll_read(cx, buf) {
-                Poll::Ready(result) => match result {
-                    Ok(()) => Poll::Ready(Ok(())),
-                    Err(error) => Poll::Ready(Err!(error)),
-                },
-                Poll::Pending => Poll::Pending,
+            Pin::new(&mut self.inner).poll_read(cx, buf)
+        }
+
+        fn poll_read_vectored(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &mut [IoSliceMut<'_>],
+        ) -> Poll<Result<usize, Self::Error>> {
+            // `tokio::io::AsyncRead` has no vectored reads, so wait for readiness and read
+            // with `readv` directly.
+            loop {
+                if let Err(error) = ready!(self.get_ref().poll_read_ready(cx)) {
+                    return Poll::Ready(Err!(error));
+                }
+                match self.get_ref().try_read_vectored(as_std_io_slices_mut(bufs)) {
+                    Ok(n) => return Poll::Ready(Ok(n)),
+                    Err(error) if error.kind() == ErrorKind::WouldBlock => continue,
+                    Err(error) => return Poll::Ready(Err!(error)),
+                }
             }
         }
+
+        fn is_read_vectored(&self) -> bool {
+            true
+        }
     }
 
     impl io::AsyncWrite for TcpAdapterTokio {
@@ -50,71 +354,197 @@ mod std_adapters {
             cx: &mut Context<'_>,
             buf: &[u8],
         ) -> Poll<Result<usize>> {
-            match Pin::new(&mut self.inner).poll_write(cx, buf) {
-                Poll::Ready(result) => match result {
-                    Ok(size) => Poll::Ready(Ok(size)),
-                    Err(error) => Poll::Ready(Err!(error)),
-                },
-                Poll::Pending => Poll::Pending,
-            }
+            Pin::new(&mut self.inner).poll_write(cx, buf)
+        }
+
+        fn poll_write_vectored(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &[IoSlice<'_>],
+        ) -> Poll<Result<usize>> {
+            Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
         }
 
         fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
-            match Pin::new(&mut self.inner).poll_flush(cx) {
-                Poll::Ready(result) => match result {
-                    Ok(()) => Poll::Ready(Ok(())),
-                    Err(error) => Poll::Ready(Err!(error)),
-                },
-                Poll::Pending => Poll::Pending,
-            }
+            Pin::new(&mut self.inner).poll_flush(cx)
         }
 
         fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
-            match Pin::new(&mut self.inner).poll_shutdown(cx) {
-                Poll::Ready(result) => match result {
-                    Ok(()) => Poll::Ready(Ok(())),
-                    Err(error) => Poll::Ready(Err!(error)),
-                },
-                Poll::Pending => Poll::Pending,
-            }
+            Pin::new(&mut self.inner).poll_shutdown(cx)
+        }
+
+        fn is_write_vectored(&self) -> bool {
+            self.inner.is_write_vectored()
         }
     }
 }
 
-// mod no_std_adapters {
-//     use alloc::borrow::Cow;
-//     use core::cell::RefCell;
-//     use core::pin::Pin;
-//     use core::task::{Context, Poll};
-//     use core::borrow::BorrowMut;
-//     use anyhow::Result;
-//     use embassy_net::tcp::TcpSocket;
-//     use embassy_net_driver::Driver;
-//     use super::AdapterConnect;
-//     use crate::core::io::ReadBuf;
-//     use crate::core::framed::IoError;
-//     use crate::core::io;
-//     use crate::core::tcp::errors::TcpError;
-//     use crate::Err;
-//
-//     pub struct TcpAdapterEmbassy<'a> {
-//         inner: RefCell<Option<TcpSocket<'a>>>,
-//     }
-//
-//     impl<'a> TcpAdapterEmbassy<'a> {
-//         pub fn new(socket: TcpSocket<'a>) -> Self {
-//             Self { inner: socket }
-//         }
-//     }
-//
-//     impl<'a> AdapterConnect<'a> for TcpAdapterEmbassy<'a>  {
-//         async fn connect(&self, ip: Cow<'a, str>) -> Result<()> {
-//             match self.inner.borrow_mut().as_mut() {
-//                 None => Err!(TcpError::UnableToConnect),
-//                 Ok(socket) => {
-//                     socket.connect
-//                 }
-//             }
-//         }
-//     }
-// }
+mod no_std_adapters {
+    use crate::core::framed::IoError;
+    use crate::core::io;
+    use crate::core::io::compat::{poll_embedded_flush, poll_embedded_read, poll_embedded_write};
+    use crate::core::io::FromEmbeddedIo;
+    use crate::core::pool::HealthCheck;
+    use crate::core::tcp::errors::TcpError;
+    use crate::core::tcp::options::TcpOptions;
+    use crate::core::tcp::state::TcpState;
+    use crate::utils::{from_ip_endpoint, to_ip_endpoint};
+    use crate::Err;
+    use anyhow::Result;
+    use core::net::SocketAddr;
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+    use embassy_net::tcp::{TcpReader, TcpSocket, TcpWriter};
+
+    #[cfg(not(feature = "std"))]
+    use crate::core::io::ReadBuf;
+    #[cfg(feature = "std")]
+    use tokio::io::ReadBuf;
+
+    pub struct TcpAdapterEmbassy<'a> {
+        pub(crate) inner: TcpSocket<'a>,
+    }
+
+    impl<'a> TcpAdapterEmbassy<'a> {
+        pub fn new(socket: TcpSocket<'a>) -> Self {
+            Self { inner: socket }
+        }
+
+        pub async fn connect(&mut self, socket_addr: SocketAddr) -> Result<()> {
+            match self.inner.connect(to_ip_endpoint(socket_addr)).await {
+                Err(_) => Err!(TcpError::UnableToConnect),
+                Ok(()) => Ok(()),
+            }
+        }
+
+        /// Applies `options` and connects to `socket_addr`.
+        pub async fn connect_with_options(
+            &mut self,
+            socket_addr: SocketAddr,
+            options: &TcpOptions,
+        ) -> Result<()> {
+            self.set_options(options)?;
+            self.connect(socket_addr).await
+        }
+
+        /// Applies `options` to the socket. Only keepalive and the timeout are supported by
+        /// `embassy-net`, any other option fails with [`TcpError::UnsupportedOption`] before
+        /// anything is applied.
+        pub fn set_options(&mut self, options: &TcpOptions) -> Result<()> {
+            let unsupported = match options {
+                TcpOptions {
+                    keep_alive: Some(Some(keep_alive)),
+                    ..
+                } if keep_alive.interval.is_some() => Some("keep alive interval"),
+                TcpOptions {
+                    keep_alive: Some(Some(keep_alive)),
+                    ..
+                } if keep_alive.count.is_some() => Some("keep alive count"),
+                TcpOptions {
+                    nodelay: Some(_), ..
+                } => Some("nodelay"),
+                TcpOptions {
+                    send_buffer_size: Some(_),
+                    ..
+                } => Some("send buffer size"),
+                TcpOptions {
+                    recv_buffer_size: Some(_),
+                    ..
+                } => Some("receive buffer size"),
+                TcpOptions {
+                    linger: Some(_), ..
+                } => Some("linger"),
+                TcpOptions {
+                    hop_limit: Some(_), ..
+                } => Some("hop limit"),
+                _ => None,
+            };
+            if let Some(option) = unsupported {
+                return Err!(TcpError::UnsupportedOption(option));
+            }
+
+            if let Some(keep_alive) = options.keep_alive {
+                self.inner
+                    .set_keep_alive(keep_alive.map(|keep_alive| keep_alive.idle));
+            }
+            if let Some(timeout) = options.timeout {
+                self.inner.set_timeout(timeout);
+            }
+
+            Ok(())
+        }
+
+        /// Closes the write side (sends FIN once the queued data is sent) while the socket can
+        /// still be read from.
+        pub fn shutdown_write(&mut self) {
+            self.inner.close()
+        }
+
+        pub fn local_addr(&self) -> Result<SocketAddr> {
+            match self.inner.local_endpoint() {
+                Some(endpoint) => Ok(from_ip_endpoint(endpoint)),
+                None => Err!(TcpError::NotConnected),
+            }
+        }
+
+        pub fn peer_addr(&self) -> Result<SocketAddr> {
+            match self.inner.remote_endpoint() {
+                Some(endpoint) => Ok(from_ip_endpoint(endpoint)),
+                None => Err!(TcpError::NotConnected),
+            }
+        }
+
+        pub fn state(&self) -> TcpState {
+            self.inner.state().into()
+        }
+
+        /// Splits the socket into a reading and a writing half using `TcpSocket::split`.
+        pub fn split(&mut self) -> (FromEmbeddedIo<TcpReader<'_>>, FromEmbeddedIo<TcpWriter<'_>>) {
+            let (reader, writer) = self.inner.split();
+
+            (FromEmbeddedIo::new(reader), FromEmbeddedIo::new(writer))
+        }
+
+        pub fn into_inner(self) -> TcpSocket<'a> {
+            self.inner
+        }
+    }
+
+    impl<'a> HealthCheck for TcpAdapterEmbassy<'a> {
+        /// The connection is healthy as long as neither side closed it.
+        fn is_healthy(&mut self) -> bool {
+            self.state() == TcpState::Established
+        }
+    }
+
+    impl<'a> io::AsyncRead for TcpAdapterEmbassy<'a> {
+        type Error = IoError;
+
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            poll_embedded_read(&mut self.inner, cx, buf)
+        }
+    }
+
+    impl<'a> io::AsyncWrite for TcpAdapterEmbassy<'a> {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<Result<usize>> {
+            poll_embedded_write(&mut self.inner, cx, buf)
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+            poll_embedded_flush(&mut self.inner, cx)
+        }
+
+        fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+            self.shutdown_write();
+            poll_embedded_flush(&mut self.inner, cx)
+        }
+    }
+}
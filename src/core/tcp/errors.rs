@@ -1,9 +1,22 @@
+use alloc::string::String;
 use thiserror_no_std::Error;
 
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum TcpError {
     #[error("Unable to connect to host")]
     UnableToConnect,
+    #[error("Unable to bind the listener")]
+    UnableToBind,
+    #[error("Unable to accept connection")]
+    UnableToAccept,
+    #[error("Tried to reunite halves of different connections")]
+    UnrelatedHalves,
+    #[error("The socket is not connected")]
+    NotConnected,
+    #[error("Unable to set socket option: {0}")]
+    UnableToSetOption(String),
+    #[error("Socket option is not supported by this adapter: {0}")]
+    UnsupportedOption(&'static str),
 }
 
 #[cfg(feature = "std")]
@@ -0,0 +1,329 @@
+pub use no_std_listeners::{PoolSlot, TcpConnectionEmbassy, TcpListenerEmbassy, TcpSocketPool};
+#[cfg(feature = "std")]
+pub use std_listeners::{TcpConnectionTokio, TcpListenerTokio};
+
+use crate::core::io::io_slice::{IoSlice, IoSliceMut};
+use crate::core::io::{AsyncRead, AsyncWrite};
+use anyhow::Result;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use pin_project_lite::pin_project;
+
+#[cfg(not(feature = "std"))]
+use crate::core::io::ReadBuf;
+#[cfg(feature = "std")]
+use tokio::io::ReadBuf;
+
+pin_project! {
+    /// An accepted connection. Frees its slot of the listener's connection limit when dropped.
+    #[derive(Debug)]
+    pub struct TcpConnection<T, G> {
+        #[pin]
+        inner: T,
+        guard: G,
+    }
+}
+
+impl<T, G> TcpConnection<T, G> {
+    pub(crate) fn new(inner: T, guard: G) -> Self {
+        Self { inner, guard }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T: AsyncRead, G> AsyncRead for TcpConnection<T, G> {
+    type Error = T::Error;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_read(cx, buf)
+    }
+
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize, Self::Error>> {
+        self.project().inner.poll_read_vectored(cx, bufs)
+    }
+
+    fn is_read_vectored(&self) -> bool {
+        self.inner.is_read_vectored()
+    }
+}
+
+impl<T: AsyncWrite, G> AsyncWrite for TcpConnection<T, G> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        self.project().inner.poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<Result<usize>> {
+        self.project().inner.poll_write_vectored(cx, bufs)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_listeners {
+    use super::TcpConnection;
+    use crate::core::tcp::adapters::TcpAdapterTokio;
+    use crate::core::tcp::errors::TcpError;
+    use crate::Err;
+    use alloc::sync::Arc;
+    use anyhow::Result;
+    use core::net::SocketAddr;
+    use tokio::net::{TcpListener, ToSocketAddrs};
+    use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+    /// A connection accepted by [`TcpListenerTokio`].
+    pub type TcpConnectionTokio = TcpConnection<TcpAdapterTokio, OwnedSemaphorePermit>;
+
+    impl TcpConnectionTokio {
+        pub fn get_mut(&mut self) -> &mut TcpAdapterTokio {
+            &mut self.inner
+        }
+    }
+
+    /// Accepts at most `max_connections` concurrent connections. Once the limit is reached,
+    /// [`TcpListenerTokio::accept`] waits until an accepted connection is dropped.
+    #[derive(Debug)]
+    pub struct TcpListenerTokio {
+        inner: TcpListener,
+        permits: Arc<Semaphore>,
+        max_connections: usize,
+    }
+
+    impl TcpListenerTokio {
+        pub async fn bind(addr: impl ToSocketAddrs, max_connections: usize) -> Result<Self> {
+            match TcpListener::bind(addr).await {
+                Ok(listener) => Ok(Self {
+                    inner: listener,
+                    permits: Arc::new(Semaphore::new(max_connections)),
+                    max_connections,
+                }),
+                Err(_) => Err!(TcpError::UnableToBind),
+            }
+        }
+
+        pub async fn accept(&self) -> Result<(TcpConnectionTokio, SocketAddr)> {
+            let permit = match self.permits.clone().acquire_owned().await {
+                Ok(permit) => permit,
+                Err(_) => return Err!(TcpError::UnableToAccept),
+            };
+
+            match self.inner.accept().await {
+                Ok((stream, addr)) => Ok((
+                    TcpConnection::new(TcpAdapterTokio::from(stream), permit),
+                    addr,
+                )),
+                Err(error) => Err!(error),
+            }
+        }
+
+        pub fn local_addr(&self) -> Result<SocketAddr> {
+            match self.inner.local_addr() {
+                Ok(addr) => Ok(addr),
+                Err(error) => Err!(error),
+            }
+        }
+
+        pub fn max_connections(&self) -> usize {
+            self.max_connections
+        }
+
+        /// Returns how many accepted connections are still alive.
+        pub fn active_connections(&self) -> usize {
+            self.max_connections - self.permits.available_permits()
+        }
+    }
+}
+
+mod no_std_listeners {
+    use super::TcpConnection;
+    use crate::core::io::FromEmbeddedIo;
+    use crate::core::tcp::adapters::TcpAdapterEmbassy;
+    use crate::core::tcp::errors::TcpError;
+    use crate::core::tcp::options::TcpOptions;
+    use crate::utils::from_ip_endpoint;
+    use crate::Err;
+    use anyhow::Result;
+    use core::cell::UnsafeCell;
+    use core::future::poll_fn;
+    use core::net::SocketAddr;
+    use core::sync::atomic::{AtomicBool, Ordering};
+    use core::task::{Context, Poll};
+    use embassy_net::tcp::{TcpReader, TcpSocket, TcpWriter};
+    use embassy_net::Stack;
+    use embassy_net_driver::Driver;
+    use futures::task::AtomicWaker;
+
+    /// A connection accepted by [`TcpListenerEmbassy`].
+    ///
+    /// The socket borrows the buffers of its pool slot, so it is never handed out mutably: it
+    /// could be swapped with the socket of another connection and outlive its slot.
+    pub type TcpConnectionEmbassy<'a, const N: usize, const RX: usize, const TX: usize> =
+        TcpConnection<TcpAdapterEmbassy<'a>, PoolSlot<'a, N, RX, TX>>;
+
+    impl<'a, const N: usize, const RX: usize, const TX: usize> TcpConnectionEmbassy<'a, N, RX, TX> {
+        /// See [`TcpAdapterEmbassy::set_options`].
+        pub fn set_options(&mut self, options: &TcpOptions) -> Result<()> {
+            self.inner.set_options(options)
+        }
+
+        /// See [`TcpAdapterEmbassy::shutdown_write`].
+        pub fn shutdown_write(&mut self) {
+            self.inner.shutdown_write()
+        }
+
+        /// See [`TcpAdapterEmbassy::split`].
+        pub fn split(&mut self) -> (FromEmbeddedIo<TcpReader<'_>>, FromEmbeddedIo<TcpWriter<'_>>) {
+            self.inner.split()
+        }
+    }
+
+    /// Receive and transmit buffers for up to `N` concurrent sockets. Each socket gets `RX`
+    /// and `TX` bytes. Use one pool per listener.
+    pub struct TcpSocketPool<const N: usize, const RX: usize, const TX: usize> {
+        buffers: [UnsafeCell<([u8; RX], [u8; TX])>; N],
+        used: [AtomicBool; N],
+        waker: AtomicWaker,
+    }
+
+    // Safety: a buffer is only handed out to the socket holding its slot.
+    unsafe impl<const N: usize, const RX: usize, const TX: usize> Sync for TcpSocketPool<N, RX, TX> {}
+
+    impl<const N: usize, const RX: usize, const TX: usize> Default for TcpSocketPool<N, RX, TX> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<const N: usize, const RX: usize, const TX: usize> TcpSocketPool<N, RX, TX> {
+        pub fn new() -> Self {
+            Self {
+                buffers: core::array::from_fn(|_| UnsafeCell::new(([0; RX], [0; TX]))),
+                used: core::array::from_fn(|_| AtomicBool::new(false)),
+                waker: AtomicWaker::new(),
+            }
+        }
+
+        /// Returns how many sockets are in use.
+        pub fn active_connections(&self) -> usize {
+            self.used
+                .iter()
+                .filter(|used| used.load(Ordering::Relaxed))
+                .count()
+        }
+
+        fn try_acquire(&self) -> Option<usize> {
+            self.used.iter().position(|used| {
+                used.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            })
+        }
+
+        fn poll_acquire(&self, cx: &mut Context<'_>) -> Poll<usize> {
+            if let Some(index) = self.try_acquire() {
+                return Poll::Ready(index);
+            }
+            self.waker.register(cx.waker());
+            // A slot may have been released before the waker was registered.
+            match self.try_acquire() {
+                Some(index) => Poll::Ready(index),
+                None => Poll::Pending,
+            }
+        }
+
+        fn release(&self, index: usize) {
+            self.used[index].store(false, Ordering::Release);
+            self.waker.wake();
+        }
+    }
+
+    /// A slot of a [`TcpSocketPool`]. Frees the slot when dropped.
+    pub struct PoolSlot<'a, const N: usize, const RX: usize, const TX: usize> {
+        pool: &'a TcpSocketPool<N, RX, TX>,
+        index: usize,
+    }
+
+    impl<'a, const N: usize, const RX: usize, const TX: usize> PoolSlot<'a, N, RX, TX> {
+        fn buffers(&self) -> (&'a mut [u8], &'a mut [u8]) {
+            // Safety: only this slot accesses the buffers until it's dropped, and the socket
+            // borrowing them is dropped before the slot (see `TcpConnection`'s field order).
+            // `TcpConnectionEmbassy` doesn't hand out the socket mutably, so it can't be moved
+            // out of its connection.
+            let (rx, tx) = unsafe { &mut *self.pool.buffers[self.index].get() };
+            (&mut rx[..], &mut tx[..])
+        }
+    }
+
+    impl<const N: usize, const RX: usize, const TX: usize> Drop for PoolSlot<'_, N, RX, TX> {
+        fn drop(&mut self) {
+            self.pool.release(self.index)
+        }
+    }
+
+    /// Accepts connections on `port`, one socket of the `pool` per connection. Once all
+    /// sockets are in use, [`TcpListenerEmbassy::accept`] waits until a connection is dropped.
+    pub struct TcpListenerEmbassy<'a, D: Driver, const N: usize, const RX: usize, const TX: usize> {
+        stack: &'a Stack<D>,
+        pool: &'a TcpSocketPool<N, RX, TX>,
+        port: u16,
+    }
+
+    impl<'a, D: Driver, const N: usize, const RX: usize, const TX: usize>
+        TcpListenerEmbassy<'a, D, N, RX, TX>
+    {
+        pub fn new(stack: &'a Stack<D>, pool: &'a TcpSocketPool<N, RX, TX>, port: u16) -> Self {
+            Self { stack, pool, port }
+        }
+
+        pub fn port(&self) -> u16 {
+            self.port
+        }
+
+        pub async fn accept(
+            &mut self,
+        ) -> Result<(TcpConnectionEmbassy<'a, N, RX, TX>, SocketAddr)> {
+            let pool = self.pool;
+            let index = poll_fn(|cx| pool.poll_acquire(cx)).await;
+            let slot = PoolSlot { pool, index };
+
+            let (rx_buffer, tx_buffer) = slot.buffers();
+            let mut socket = TcpSocket::new(self.stack, rx_buffer, tx_buffer);
+            if socket.accept(self.port).await.is_err() {
+                return Err!(TcpError::UnableToAccept);
+            }
+            let remote = match socket.remote_endpoint() {
+                Some(endpoint) => from_ip_endpoint(endpoint),
+                None => return Err!(TcpError::UnableToAccept),
+            };
+
+            Ok((
+                TcpConnection::new(TcpAdapterEmbassy::new(socket), slot),
+                remote,
+            ))
+        }
+    }
+}
@@ -4,11 +4,8 @@
 // use anyhow::Result;
 // use core::borrow::BorrowMut;
 // use core::fmt::Debug;
-// use core::future::poll_fn;
 // use core::pin::Pin;
 // use core::task::{Context, Poll};
-// use embedded_io::asynch::{Read, Write};
-// use embedded_io::Io;
 
 // #[cfg(not(feature = "std"))]
 // use crate::core::io::ReadBuf;
@@ -18,6 +15,12 @@
 
 pub mod adapters;
 pub mod errors;
+pub mod listeners;
+pub mod options;
+pub mod state;
+
+pub use options::{KeepAlive, TcpOptions};
+pub use state::TcpState;
 
 // // TODO: utilize to check `state`
 // pub struct Socket;
@@ -84,41 +87,6 @@ pub mod errors;
 //     }
 // }
 
-// impl<T> Io for TcpSocket<T> {
-//     type Error = IoError;
-// }
-
-// impl<T> Read for TcpSocket<T>
-// where
-//     T: AsyncRead + Unpin,
-// {
-//     async fn read(&mut self, buf: &mut [u8]) -> core::result::Result<usize, Self::Error> {
-//         let size = buf.len();
-//         poll_fn(|cx| Pin::new(&mut self.socket).poll_read(cx, ReadBuf::new(buf).borrow_mut()))
-//             .await
-//             .map_err(|_| IoError::UnableToRead)?;
-
-//         Ok(size)
-//     }
-// }
-
-// impl<T> Write for TcpSocket<T>
-// where
-//     T: AsyncWrite + Unpin,
-// {
-//     async fn write(&mut self, buf: &[u8]) -> core::result::Result<usize, Self::Error> {
-//         let size = buf.len();
-//         poll_fn(|cx| Pin::new(&mut self.socket).poll_write(cx, buf))
-//             .await
-//             .map_err(|_| IoError::UnableToWrite)?;
-//         poll_fn(|cx| Pin::new(&mut self.socket).poll_flush(cx))
-//             .await
-//             .map_err(|_| IoError::UnableToFlush)?;
-
-//         Ok(size)
-//     }
-// }
-
 // pub trait TcpConnect<'a> {
 //     async fn connect(&mut self, ip: Cow<'a, str>) -> Result<()>;
 // }
@@ -0,0 +1,93 @@
+//! Socket options for the TCP adapters. Options set to `None` keep the current value.
+
+use embassy_time::Duration;
+
+/// TCP keepalive probes, sent after the connection has been idle for `idle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeepAlive {
+    pub idle: Duration,
+    /// Time between unanswered probes. Not supported by `embassy-net`, which probes every `idle`.
+    pub interval: Option<Duration>,
+    /// Unanswered probes before the connection is dropped. Not supported by `embassy-net`.
+    pub count: Option<u32>,
+}
+
+impl KeepAlive {
+    pub fn new(idle: Duration) -> Self {
+        Self {
+            idle,
+            interval: None,
+            count: None,
+        }
+    }
+
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    pub fn count(mut self, count: u32) -> Self {
+        self.count = Some(count);
+        self
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TcpOptions {
+    /// `Some(None)` disables keepalive.
+    pub keep_alive: Option<Option<KeepAlive>>,
+    /// Disables Nagle's algorithm (`TCP_NODELAY`).
+    pub nodelay: Option<bool>,
+    /// `SO_SNDBUF`. On embassy the size is given by the socket's buffer.
+    pub send_buffer_size: Option<usize>,
+    /// `SO_RCVBUF`. On embassy the size is given by the socket's buffer.
+    pub recv_buffer_size: Option<usize>,
+    /// `SO_LINGER`. `Some(None)` disables lingering.
+    pub linger: Option<Option<Duration>>,
+    /// TTL (IPv4) or unicast hop limit (IPv6) of outgoing packets.
+    pub hop_limit: Option<u8>,
+    /// How long sent data may stay unacknowledged before the connection is dropped
+    /// (`TCP_USER_TIMEOUT`, `TcpSocket::set_timeout` on embassy). `Some(None)` disables it.
+    pub timeout: Option<Option<Duration>>,
+}
+
+impl TcpOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn keep_alive(mut self, keep_alive: Option<KeepAlive>) -> Self {
+        self.keep_alive = Some(keep_alive);
+        self
+    }
+
+    pub fn nodelay(mut self, nodelay: bool) -> Self {
+        self.nodelay = Some(nodelay);
+        self
+    }
+
+    pub fn send_buffer_size(mut self, size: usize) -> Self {
+        self.send_buffer_size = Some(size);
+        self
+    }
+
+    pub fn recv_buffer_size(mut self, size: usize) -> Self {
+        self.recv_buffer_size = Some(size);
+        self
+    }
+
+    pub fn linger(mut self, linger: Option<Duration>) -> Self {
+        self.linger = Some(linger);
+        self
+    }
+
+    pub fn hop_limit(mut self, hop_limit: u8) -> Self {
+        self.hop_limit = Some(hop_limit);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
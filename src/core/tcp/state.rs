@@ -0,0 +1,69 @@
+/// The state of a TCP connection, mirroring `embassy_net::tcp::State` (RFC 793).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TcpState {
+    Closed,
+    Listen,
+    SynSent,
+    SynReceived,
+    Established,
+    FinWait1,
+    FinWait2,
+    /// The peer closed its write side (sent FIN), reads return EOF once drained.
+    CloseWait,
+    Closing,
+    LastAck,
+    TimeWait,
+}
+
+impl TcpState {
+    /// Returns `true` if data can still be sent.
+    pub fn may_send(&self) -> bool {
+        matches!(self, Self::Established | Self::CloseWait)
+    }
+
+    /// Returns `true` if the peer may still send data.
+    pub fn may_recv(&self) -> bool {
+        matches!(self, Self::Established | Self::FinWait1 | Self::FinWait2)
+    }
+}
+
+impl From<embassy_net::tcp::State> for TcpState {
+    fn from(state: embassy_net::tcp::State) -> Self {
+        use embassy_net::tcp::State;
+
+        match state {
+            State::Closed => Self::Closed,
+            State::Listen => Self::Listen,
+            State::SynSent => Self::SynSent,
+            State::SynReceived => Self::SynReceived,
+            State::Established => Self::Established,
+            State::FinWait1 => Self::FinWait1,
+            State::FinWait2 => Self::FinWait2,
+            State::CloseWait => Self::CloseWait,
+            State::Closing => Self::Closing,
+            State::LastAck => Self::LastAck,
+            State::TimeWait => Self::TimeWait,
+        }
+    }
+}
+
+/// Maps `tcp_info::tcpi_state` of Linux (`include/net/tcp_states.h`).
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub(crate) fn from_linux_tcp_state(state: u8) -> Option<TcpState> {
+    let state = match state {
+        1 => TcpState::Established,
+        2 => TcpState::SynSent,
+        3 => TcpState::SynReceived,
+        4 => TcpState::FinWait1,
+        5 => TcpState::FinWait2,
+        6 => TcpState::TimeWait,
+        7 => TcpState::Closed,
+        8 => TcpState::CloseWait,
+        9 => TcpState::LastAck,
+        10 => TcpState::Listen,
+        11 => TcpState::Closing,
+        _ => return None,
+    };
+
+    Some(state)
+}
@@ -0,0 +1,224 @@
+pub use no_std_adapters::UdpAdapterEmbassy;
+#[cfg(feature = "std")]
+pub use std_adapters::UdpAdapterTokio;
+
+#[cfg(feature = "std")]
+mod std_adapters {
+    use crate::core::udp::UdpSocket;
+    use crate::Err;
+    use anyhow::Result;
+    use core::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use tokio::net::ToSocketAddrs;
+
+    #[derive(Debug)]
+    pub struct UdpAdapterTokio {
+        pub(crate) inner: tokio::net::UdpSocket,
+    }
+
+    impl UdpAdapterTokio {
+        pub async fn bind(addr: impl ToSocketAddrs) -> Result<Self> {
+            match tokio::net::UdpSocket::bind(addr).await {
+                Ok(socket) => Ok(Self { inner: socket }),
+                Err(error) => Err!(error),
+            }
+        }
+
+        pub fn into_inner(self) -> tokio::net::UdpSocket {
+            self.inner
+        }
+    }
+
+    impl UdpSocket for UdpAdapterTokio {
+        /// Replaces the socket with a new one bound to `addr`.
+        async fn rebind(&mut self, addr: SocketAddr) -> Result<()> {
+            *self = Self::bind(addr).await?;
+            Ok(())
+        }
+
+        async fn connect(&mut self, addr: SocketAddr) -> Result<()> {
+            match self.inner.connect(addr).await {
+                Ok(()) => Ok(()),
+                Err(error) => Err!(error),
+            }
+        }
+
+        async fn send_to(&self, buf: &[u8], target: SocketAddr) -> Result<usize> {
+            match self.inner.send_to(buf, target).await {
+                Ok(size) => Ok(size),
+                Err(error) => Err!(error),
+            }
+        }
+
+        async fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
+            match self.inner.recv_from(buf).await {
+                Ok(received) => Ok(received),
+                Err(error) => Err!(error),
+            }
+        }
+
+        async fn send(&self, buf: &[u8]) -> Result<usize> {
+            match self.inner.send(buf).await {
+                Ok(size) => Ok(size),
+                Err(error) => Err!(error),
+            }
+        }
+
+        async fn recv(&self, buf: &mut [u8]) -> Result<usize> {
+            match self.inner.recv(buf).await {
+                Ok(size) => Ok(size),
+                Err(error) => Err!(error),
+            }
+        }
+
+        async fn join_multicast(&mut self, group: IpAddr) -> Result<()> {
+            let result = match group {
+                IpAddr::V4(group) => self.inner.join_multicast_v4(group, Ipv4Addr::UNSPECIFIED),
+                IpAddr::V6(group) => self.inner.join_multicast_v6(&group, 0),
+            };
+            match result {
+                Ok(()) => Ok(()),
+                Err(error) => Err!(error),
+            }
+        }
+
+        async fn leave_multicast(&mut self, group: IpAddr) -> Result<()> {
+            let result = match group {
+                IpAddr::V4(group) => self.inner.leave_multicast_v4(group, Ipv4Addr::UNSPECIFIED),
+                IpAddr::V6(group) => self.inner.leave_multicast_v6(&group, 0),
+            };
+            match result {
+                Ok(()) => Ok(()),
+                Err(error) => Err!(error),
+            }
+        }
+
+        fn local_addr(&self) -> Result<SocketAddr> {
+            match self.inner.local_addr() {
+                Ok(addr) => Ok(addr),
+                Err(error) => Err!(error),
+            }
+        }
+    }
+}
+
+mod no_std_adapters {
+    use crate::core::udp::errors::UdpError;
+    use crate::core::udp::UdpSocket;
+    use crate::utils::{from_ip_address, from_ip_endpoint, to_ip_address, to_ip_endpoint};
+    use crate::Err;
+    use anyhow::Result;
+    use core::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use embassy_net::{IpEndpoint, IpListenEndpoint, Stack};
+    use embassy_net_driver::Driver;
+
+    /// Wraps an `embassy_net::udp::UdpSocket`. `embassy-net` joins multicast groups on the
+    /// `Stack`, which therefore has to be passed along with the socket.
+    pub struct UdpAdapterEmbassy<'a, D: Driver + 'static> {
+        pub(crate) inner: embassy_net::udp::UdpSocket<'a>,
+        stack: &'a Stack<D>,
+        peer: Option<IpEndpoint>,
+    }
+
+    impl<'a, D: Driver + 'static> UdpAdapterEmbassy<'a, D> {
+        /// Wraps `socket`, which was created on `stack`.
+        pub fn new(stack: &'a Stack<D>, socket: embassy_net::udp::UdpSocket<'a>) -> Self {
+            Self {
+                inner: socket,
+                stack,
+                peer: None,
+            }
+        }
+
+        pub fn into_inner(self) -> embassy_net::udp::UdpSocket<'a> {
+            self.inner
+        }
+    }
+
+    impl<'a, D: Driver + 'static> UdpSocket for UdpAdapterEmbassy<'a, D> {
+        async fn rebind(&mut self, addr: SocketAddr) -> Result<()> {
+            if self.inner.is_open() {
+                self.inner.close();
+            }
+            let endpoint = IpListenEndpoint {
+                addr: match addr.ip().is_unspecified() {
+                    true => None,
+                    false => Some(to_ip_address(addr.ip())),
+                },
+                port: addr.port(),
+            };
+            match self.inner.bind(endpoint) {
+                Ok(()) => Ok(()),
+                Err(_) => Err!(UdpError::UnableToBind),
+            }
+        }
+
+        /// Only remembers the peer, the socket has to be bound already.
+        async fn connect(&mut self, addr: SocketAddr) -> Result<()> {
+            if !self.inner.is_open() {
+                return Err!(UdpError::UnableToConnect);
+            }
+            self.peer = Some(to_ip_endpoint(addr));
+
+            Ok(())
+        }
+
+        async fn send_to(&self, buf: &[u8], target: SocketAddr) -> Result<usize> {
+            match self.inner.send_to(buf, to_ip_endpoint(target)).await {
+                Ok(()) => Ok(buf.len()),
+                Err(_) => Err!(UdpError::UnableToSend),
+            }
+        }
+
+        async fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
+            match self.inner.recv_from(buf).await {
+                Ok((size, endpoint)) => Ok((size, from_ip_endpoint(endpoint))),
+                Err(_) => Err!(UdpError::UnableToReceive),
+            }
+        }
+
+        async fn send(&self, buf: &[u8]) -> Result<usize> {
+            match self.peer {
+                Some(peer) => self.send_to(buf, from_ip_endpoint(peer)).await,
+                None => Err!(UdpError::NotConnected),
+            }
+        }
+
+        async fn recv(&self, buf: &mut [u8]) -> Result<usize> {
+            let peer = match self.peer {
+                Some(peer) => from_ip_endpoint(peer),
+                None => return Err!(UdpError::NotConnected),
+            };
+            loop {
+                // Drop datagrams of anyone else, like a connected socket would.
+                let (size, from) = self.recv_from(buf).await?;
+                if from == peer {
+                    return Ok(size);
+                }
+            }
+        }
+
+        async fn join_multicast(&mut self, group: IpAddr) -> Result<()> {
+            match self.stack.join_multicast_group(to_ip_address(group)).await {
+                Ok(_) => Ok(()),
+                Err(_) => Err!(UdpError::UnableToJoinMulticast),
+            }
+        }
+
+        async fn leave_multicast(&mut self, group: IpAddr) -> Result<()> {
+            match self.stack.leave_multicast_group(to_ip_address(group)).await {
+                Ok(_) => Ok(()),
+                Err(_) => Err!(UdpError::UnableToLeaveMulticast),
+            }
+        }
+
+        fn local_addr(&self) -> Result<SocketAddr> {
+            let endpoint = self.inner.endpoint();
+            let ip = match endpoint.addr {
+                Some(addr) => from_ip_address(addr),
+                None => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            };
+
+            Ok(SocketAddr::new(ip, endpoint.port))
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use thiserror_no_std::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum UdpError {
+    #[error("Unable to bind the socket")]
+    UnableToBind,
+    #[error("Unable to connect to host")]
+    UnableToConnect,
+    #[error("Unable to send datagram")]
+    UnableToSend,
+    #[error("Unable to receive datagram")]
+    UnableToReceive,
+    #[error("Tried to send or receive without a connected peer")]
+    NotConnected,
+    #[error("Unable to join multicast group")]
+    UnableToJoinMulticast,
+    #[error("Unable to leave multicast group")]
+    UnableToLeaveMulticast,
+}
+
+#[cfg(feature = "std")]
+impl alloc::error::Error for UdpError {}
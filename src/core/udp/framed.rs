@@ -0,0 +1,98 @@
+//! A no_std version of `tokio_util::udp::UdpFramed`
+//!
+//! `<https://github.com/tokio-rs/tokio/blob/master/tokio-util/src/udp/frame.rs>`
+//!
+//! Every datagram holds exactly one frame: items are encoded into a single datagram and each
+//! received datagram is decoded on its own.
+
+use crate::core::framed::codec::{Decoder, Encoder};
+use crate::core::udp::UdpSocket;
+use crate::Err;
+use anyhow::Result;
+use bytes::BytesMut;
+use core::net::SocketAddr;
+
+/// The largest possible UDP payload.
+const INITIAL_RD_CAPACITY: usize = 64 * 1024;
+const INITIAL_WR_CAPACITY: usize = 8 * 1024;
+
+#[derive(Debug)]
+pub struct UdpFramed<T, C> {
+    socket: T,
+    codec: C,
+    read_buffer: BytesMut,
+    write_buffer: BytesMut,
+    max_datagram_size: usize,
+}
+
+impl<T: UdpSocket, C> UdpFramed<T, C> {
+    pub fn new(socket: T, codec: C) -> Self {
+        Self::with_max_datagram_size(socket, codec, INITIAL_RD_CAPACITY)
+    }
+
+    /// Like [`UdpFramed::new`], but receives datagrams of at most `max_datagram_size` bytes.
+    /// Longer datagrams are truncated by the socket.
+    pub fn with_max_datagram_size(socket: T, codec: C, max_datagram_size: usize) -> Self {
+        Self {
+            socket,
+            codec,
+            read_buffer: BytesMut::with_capacity(max_datagram_size),
+            write_buffer: BytesMut::with_capacity(INITIAL_WR_CAPACITY),
+            max_datagram_size,
+        }
+    }
+
+    /// Encodes `item` into a single datagram and sends it to `target`.
+    pub async fn send<I>(&mut self, item: I, target: SocketAddr) -> Result<()>
+    where
+        C: Encoder<I>,
+    {
+        self.write_buffer.clear();
+        if let Err(error) = self.codec.encode(item, &mut self.write_buffer) {
+            return Err!(error);
+        }
+        self.socket.send_to(&self.write_buffer, target).await?;
+
+        Ok(())
+    }
+
+    /// Receives datagrams until one decodes into a frame and returns it with its sender.
+    pub async fn recv(&mut self) -> Result<(C::Item, SocketAddr)>
+    where
+        C: Decoder,
+    {
+        loop {
+            self.read_buffer.clear();
+            self.read_buffer.resize(self.max_datagram_size, 0);
+            let (size, from) = self.socket.recv_from(&mut self.read_buffer).await?;
+            self.read_buffer.truncate(size);
+
+            match self.codec.decode_eof(&mut self.read_buffer) {
+                Ok(Some(frame)) => return Ok((frame, from)),
+                // Empty datagram
+                Ok(None) => continue,
+                Err(error) => return Err!(error),
+            }
+        }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.socket
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.socket
+    }
+
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    pub fn codec_mut(&mut self) -> &mut C {
+        &mut self.codec
+    }
+
+    pub fn into_inner(self) -> T {
+        self.socket
+    }
+}
@@ -0,0 +1,35 @@
+use anyhow::Result;
+use core::net::{IpAddr, SocketAddr};
+
+pub mod adapters;
+pub mod errors;
+pub mod framed;
+
+pub use framed::UdpFramed;
+
+/// The operations shared by all UDP socket adapters.
+pub trait UdpSocket {
+    /// Binds the socket to `addr`, replacing a previous binding. Port `0` picks a free port.
+    async fn rebind(&mut self, addr: SocketAddr) -> Result<()>;
+
+    /// Sets the default peer for [`UdpSocket::send`] and only receives datagrams from it.
+    async fn connect(&mut self, addr: SocketAddr) -> Result<()>;
+
+    /// Sends `buf` as a single datagram to `target`, returning how many bytes were sent.
+    async fn send_to(&self, buf: &[u8], target: SocketAddr) -> Result<usize>;
+
+    /// Receives a single datagram into `buf`, returning its length and sender.
+    async fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr)>;
+
+    /// Sends `buf` to the peer set by [`UdpSocket::connect`].
+    async fn send(&self, buf: &[u8]) -> Result<usize>;
+
+    /// Receives a datagram from the peer set by [`UdpSocket::connect`].
+    async fn recv(&self, buf: &mut [u8]) -> Result<usize>;
+
+    async fn join_multicast(&mut self, group: IpAddr) -> Result<()>;
+
+    async fn leave_multicast(&mut self, group: IpAddr) -> Result<()>;
+
+    fn local_addr(&self) -> Result<SocketAddr>;
+}
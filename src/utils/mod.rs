@@ -6,3 +6,30 @@ macro_rules! singleton {
         STATIC_CELL.init_with(move || $val)
     }};
 }
+
+pub(crate) mod lock;
+
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use embassy_net::{IpAddress, IpEndpoint, Ipv4Address, Ipv6Address};
+
+pub(crate) fn to_ip_endpoint(socket_addr: SocketAddr) -> IpEndpoint {
+    IpEndpoint::new(to_ip_address(socket_addr.ip()), socket_addr.port())
+}
+
+pub(crate) fn from_ip_endpoint(endpoint: IpEndpoint) -> SocketAddr {
+    SocketAddr::new(from_ip_address(endpoint.addr), endpoint.port)
+}
+
+pub(crate) fn to_ip_address(ip: IpAddr) -> IpAddress {
+    match ip {
+        IpAddr::V4(ip) => IpAddress::Ipv4(Ipv4Address::from_bytes(&ip.octets())),
+        IpAddr::V6(ip) => IpAddress::Ipv6(Ipv6Address::from_bytes(&ip.octets())),
+    }
+}
+
+pub(crate) fn from_ip_address(address: IpAddress) -> IpAddr {
+    match address {
+        IpAddress::Ipv4(ip) => IpAddr::V4(Ipv4Addr::from(ip.0)),
+        IpAddress::Ipv6(ip) => IpAddr::V6(Ipv6Addr::from(ip.0)),
+    }
+}
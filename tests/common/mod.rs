@@ -1,4 +1,3 @@
-pub mod codec;
 mod constants;
 
 pub use constants::*;
@@ -6,9 +5,12 @@ use em_as_net::client::websocket::{
     AsyncWebsocketClientEmbeddedWebsocketTokio, AsyncWebsocketClientTungstenite,
     EmbeddedWebsocketOptions, WebsocketOpen,
 };
+use em_as_net::core::framed::{Codec, Framed};
+use em_as_net::core::io::TokioCompat;
 use rand::{rngs::ThreadRng, thread_rng};
-use tokio::net::TcpStream;
-use tokio_util::codec::Framed;
+use std::net::SocketAddr;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
 
 pub async fn connect_to_ws_tungstenite_echo<'a>() -> AsyncWebsocketClientTungstenite<WebsocketOpen>
 {
@@ -31,7 +33,7 @@ pub async fn connect_to_tungstenite_wss_echo<'a>() -> AsyncWebsocketClientTungst
 }
 
 pub async fn connect_to_embedded_websocket_tokio_ws_echo<'a>(
-    stream: &'a mut Framed<TcpStream, codec::Codec>,
+    stream: &'a mut Framed<TokioCompat<TcpStream>, Codec>,
     buffer: &'a mut [u8],
     websocket_options: &'a EmbeddedWebsocketOptions<'a>,
 ) -> AsyncWebsocketClientEmbeddedWebsocketTokio<ThreadRng, WebsocketOpen> {
@@ -46,3 +48,35 @@ pub async fn connect_to_embedded_websocket_tokio_ws_echo<'a>(
 
     websocket
 }
+
+/// Spawns a local HTTP server which answers every websocket upgrade request with
+/// `403 Forbidden`.
+pub async fn spawn_refusing_websocket_server() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = match listener.accept().await {
+                Ok(connection) => connection,
+                Err(_) => return,
+            };
+            tokio::spawn(async move {
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => request.extend_from_slice(&buffer[..n]),
+                    }
+                }
+                let _ = stream
+                    .write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n")
+                    .await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+
+    addr
+}
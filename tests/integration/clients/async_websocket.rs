@@ -1,16 +1,19 @@
 use crate::common::connect_to_embedded_websocket_tokio_ws_echo;
 use crate::common::{
-    codec::Codec, connect_to_tungstenite_wss_echo, connect_to_ws_tungstenite_echo,
-    ECHO_WS_AS_IP_SERVER,
+    connect_to_tungstenite_wss_echo, connect_to_ws_tungstenite_echo,
+    spawn_refusing_websocket_server, ECHO_WS_AS_IP_SERVER,
 };
 
 use em_as_net::client::websocket::{
+    AsyncWebsocketClientEmbeddedWebsocketTokio, AsyncWebsocketClientTungstenite,
     EmbeddedWebsocketOptions, EmbeddedWebsocketReadMessageType, EmbeddedWebsocketSendMessageType,
     TungsteniteMessage,
 };
+use em_as_net::core::framed::{Codec, Framed};
+use em_as_net::core::io::TokioCompat;
 use futures::{SinkExt, TryStreamExt};
+use rand::thread_rng;
 use tokio::net::TcpStream;
-use tokio_util::codec::Framed;
 
 #[tokio::test]
 async fn test_websocket_non_tls() {
@@ -55,7 +58,7 @@ async fn test_websocket_tls() {
 #[tokio::test]
 async fn test_websocket_embedded_ws_tokio() {
     let stream = TcpStream::connect(ECHO_WS_AS_IP_SERVER).await.unwrap();
-    let mut framed = Framed::new(stream, Codec::new());
+    let mut framed = Framed::new(TokioCompat::new(stream), Codec::new());
     let mut buffer = [0u8; 4096];
     let websocket_options = EmbeddedWebsocketOptions {
         path: "/mirror",
@@ -98,3 +101,41 @@ async fn test_websocket_embedded_ws_tokio() {
         }
     }
 }
+
+#[tokio::test]
+async fn test_websocket_tungstenite_upgrade_refused() {
+    let addr = spawn_refusing_websocket_server().await;
+    let uri = format!("ws://{}/", addr).parse().unwrap();
+
+    let error = AsyncWebsocketClientTungstenite::open(uri)
+        .await
+        .err()
+        .expect("Expected the refused upgrade to return an error.");
+    assert!(error.to_string().contains("403"));
+}
+
+#[tokio::test]
+async fn test_websocket_embedded_ws_tokio_upgrade_refused() {
+    let addr = spawn_refusing_websocket_server().await;
+    let stream = TcpStream::connect(addr).await.unwrap();
+    let mut framed = Framed::new(TokioCompat::new(stream), Codec::new());
+    let mut buffer = [0u8; 4096];
+    let websocket_options = EmbeddedWebsocketOptions {
+        path: "/",
+        host: "localhost",
+        origin: "http://localhost",
+        sub_protocols: None,
+        additional_headers: None,
+    };
+
+    let error = AsyncWebsocketClientEmbeddedWebsocketTokio::open(
+        &mut framed,
+        &mut buffer,
+        thread_rng(),
+        &websocket_options,
+    )
+    .await
+    .err()
+    .expect("Expected the refused upgrade to return an error.");
+    assert!(error.to_string().contains("403"));
+}
@@ -0,0 +1,76 @@
+use bytes::BytesMut;
+use em_as_net::core::framed::codec::{
+    AnyDelimiterCodec, Decoder, Encoder, HeaplessLinesCodec, LinesCodec,
+};
+use em_as_net::core::framed::IoError;
+use em_as_net::core::io::{duplex, AsyncWriteExt};
+use futures::{SinkExt, StreamExt};
+
+#[test]
+fn test_lines_codec_line_endings() {
+    let mut codec = LinesCodec::new();
+    let mut buf = BytesMut::from("AT\r\nOK\nERR");
+
+    assert_eq!(Some("AT".to_string()), codec.decode(&mut buf).unwrap());
+    assert_eq!(Some("OK".to_string()), codec.decode(&mut buf).unwrap());
+    assert_eq!(None, codec.decode(&mut buf).unwrap());
+    assert_eq!(Some("ERR".to_string()), codec.decode_eof(&mut buf).unwrap());
+    assert_eq!(None, codec.decode_eof(&mut buf).unwrap());
+
+    let mut codec = LinesCodec::new().with_crlf();
+    let mut buf = BytesMut::new();
+    codec.encode("AT+CSQ", &mut buf).unwrap();
+    assert_eq!(b"AT+CSQ\r\n", &buf[..]);
+}
+
+#[test]
+fn test_lines_codec_max_length_and_utf8() {
+    let mut codec = LinesCodec::new_with_max_length(4);
+    let mut buf = BytesMut::from("too long\nok\n");
+
+    assert!(matches!(codec.decode(&mut buf), Err(IoError::LineTooLong)));
+    // The rest of the long line is skipped.
+    assert_eq!(Some("ok".to_string()), codec.decode(&mut buf).unwrap());
+
+    let mut buf = BytesMut::from(&b"\xff\xfe\n"[..]);
+    assert!(matches!(codec.decode(&mut buf), Err(IoError::InvalidUtf8)));
+}
+
+#[test]
+fn test_heapless_lines_codec() {
+    let mut codec = HeaplessLinesCodec::<4>::new();
+    let mut buf = BytesMut::from("$GPS\r\n$GPGGA\n");
+
+    assert_eq!("$GPS", codec.decode(&mut buf).unwrap().unwrap().as_str());
+    assert!(matches!(codec.decode(&mut buf), Err(IoError::LineTooLong)));
+}
+
+#[test]
+fn test_any_delimiter_codec() {
+    let mut codec = AnyDelimiterCodec::new_with_max_length(b",;".to_vec(), b";".to_vec(), 8);
+    let mut buf = BytesMut::from("a,b;,c");
+
+    assert_eq!(b"a", &codec.decode(&mut buf).unwrap().unwrap()[..]);
+    assert_eq!(b"b", &codec.decode(&mut buf).unwrap().unwrap()[..]);
+    assert_eq!(b"", &codec.decode(&mut buf).unwrap().unwrap()[..]);
+    assert_eq!(None, codec.decode(&mut buf).unwrap());
+    assert_eq!(b"c", &codec.decode_eof(&mut buf).unwrap().unwrap()[..]);
+
+    let mut buf = BytesMut::new();
+    codec.encode("d", &mut buf).unwrap();
+    assert_eq!(b"d;", &buf[..]);
+}
+
+#[tokio::test]
+async fn test_lines_codec_framed() {
+    let (mut a, b) = duplex(64);
+    let mut b = LinesCodec::new().framed(b);
+
+    a.write_all(b"hello\r\nworld").await.unwrap();
+    a.shutdown().await.unwrap();
+    assert_eq!("hello", b.next().await.unwrap().unwrap());
+    assert_eq!("world", b.next().await.unwrap().unwrap());
+    assert!(b.next().await.is_none());
+
+    b.send("bye").await.unwrap();
+}
@@ -0,0 +1,83 @@
+use bytes::BytesMut;
+use em_as_net::core::framed::codec::{Checksum, Decoder, Encoder, HdlcCodec};
+use em_as_net::core::framed::IoError;
+use em_as_net::core::io::{duplex, AsyncWriteExt};
+use futures::StreamExt;
+
+#[test]
+fn test_hdlc_check_values() {
+    // The check values of CRC-16/X-25 (0x906e) and CRC-32 (0xcbf43926).
+    let mut buf = BytesMut::new();
+    HdlcCodec::new(Checksum::Crc16)
+        .encode("123456789", &mut buf)
+        .unwrap();
+    assert_eq!(b"\x7e123456789\x6e\x90\x7e", &buf[..]);
+
+    let mut buf = BytesMut::new();
+    HdlcCodec::new(Checksum::Crc32)
+        .encode("123456789", &mut buf)
+        .unwrap();
+    assert_eq!(b"\x7e123456789\x26\x39\xf4\xcb\x7e", &buf[..]);
+}
+
+#[test]
+fn test_hdlc_round_trip_with_escapes() {
+    for checksum in [Checksum::Crc16, Checksum::Crc32] {
+        let mut codec = HdlcCodec::new(checksum);
+        let payload = [0x7e, 0x00, 0x7d, 0x5e, 0x7e];
+        let mut buf = BytesMut::new();
+        codec.encode(&payload[..], &mut buf).unwrap();
+        codec.encode(&b""[..], &mut buf).unwrap();
+
+        assert_eq!(&payload[..], &codec.decode(&mut buf).unwrap().unwrap()[..]);
+        assert!(codec.decode(&mut buf).unwrap().unwrap().is_empty());
+        assert!(buf.is_empty());
+    }
+}
+
+#[test]
+fn test_hdlc_checksum_mismatch_is_recoverable() {
+    let mut codec = HdlcCodec::new(Checksum::Crc16);
+    let mut buf = BytesMut::new();
+    codec.encode("corrupt", &mut buf).unwrap();
+    buf[3] ^= 0x01;
+    codec.encode("intact", &mut buf).unwrap();
+
+    let error = codec.decode(&mut buf).unwrap_err();
+    assert!(matches!(error, IoError::ChecksumMismatch));
+    assert!(codec.is_recoverable(&error));
+    assert_eq!(b"intact", &codec.decode(&mut buf).unwrap().unwrap()[..]);
+}
+
+#[test]
+fn test_hdlc_max_frame_length() {
+    let mut codec = HdlcCodec::new_with_max_length(Checksum::Crc32, 2);
+    assert!(matches!(
+        codec.encode("abc", &mut BytesMut::new()),
+        Err(IoError::FrameTooLong)
+    ));
+
+    let mut buf = BytesMut::new();
+    HdlcCodec::new(Checksum::Crc32)
+        .encode("abc", &mut buf)
+        .unwrap();
+    assert!(matches!(codec.decode(&mut buf), Err(IoError::FrameTooLong)));
+}
+
+#[tokio::test]
+async fn test_framed_resynchronises_after_checksum_mismatch() {
+    let (mut a, b) = duplex(64);
+    let mut b = HdlcCodec::new(Checksum::Crc16).framed(b);
+
+    let mut frames = BytesMut::new();
+    let mut codec = HdlcCodec::new(Checksum::Crc16);
+    codec.encode("first", &mut frames).unwrap();
+    frames[2] = b'X';
+    codec.encode("second", &mut frames).unwrap();
+    a.write_all(&frames).await.unwrap();
+    a.shutdown().await.unwrap();
+
+    assert!(b.next().await.unwrap().is_err());
+    assert_eq!(b"second", &b.next().await.unwrap().unwrap()[..]);
+    assert!(b.next().await.is_none());
+}
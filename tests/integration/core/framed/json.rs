@@ -0,0 +1,124 @@
+use bytes::BytesMut;
+use em_as_net::core::framed::codec::{
+    Decoder, Encoder, HeaplessJsonCodec, JsonCodec, LengthDelimitedCodec,
+};
+use em_as_net::core::framed::IoError;
+use em_as_net::core::io::{duplex, AsyncWriteExt};
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Message {
+    id: u32,
+    method: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct HeaplessMessage {
+    id: u32,
+    method: heapless::String<16>,
+}
+
+fn message(id: u32) -> Message {
+    Message {
+        id,
+        method: "ping\n".into(),
+    }
+}
+
+#[test]
+fn test_json_lines_round_trip() {
+    let mut codec = JsonCodec::<Message>::new();
+    let mut buf = BytesMut::new();
+    codec.encode(message(1), &mut buf).unwrap();
+    codec.encode(message(2), &mut buf).unwrap();
+    assert_eq!(
+        b"{\"id\":1,\"method\":\"ping\\n\"}\n{\"id\":2,\"method\":\"ping\\n\"}\n",
+        &buf[..]
+    );
+
+    assert_eq!(Some(message(1)), codec.decode(&mut buf).unwrap());
+    assert_eq!(Some(message(2)), codec.decode(&mut buf).unwrap());
+    assert_eq!(None, codec.decode(&mut buf).unwrap());
+}
+
+#[test]
+fn test_json_invalid_line_is_recoverable() {
+    let mut codec = JsonCodec::<Message>::new();
+    let mut buf = BytesMut::from(&b"{\"id\":\"one\"}\n{\"id\":1,\"method\":\"ping\\n\"}\n"[..]);
+
+    let error = codec.decode(&mut buf).unwrap_err();
+    assert!(matches!(error, IoError::InvalidJson));
+    assert!(codec.is_recoverable(&error));
+    assert_eq!(Some(message(1)), codec.decode(&mut buf).unwrap());
+}
+
+#[test]
+fn test_json_length_delimited_round_trip() {
+    let mut codec = JsonCodec::<Message>::length_delimited(
+        LengthDelimitedCodec::builder()
+            .length_field_length(2)
+            .new_codec(),
+    );
+    let mut buf = BytesMut::new();
+    codec.encode(message(1), &mut buf).unwrap();
+    assert_eq!(b"\x00\x1a{\"id\":1,\"method\":\"ping\\n\"}", &buf[..]);
+
+    assert_eq!(Some(message(1)), codec.decode(&mut buf).unwrap());
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_heapless_json_round_trip() {
+    let mut codec = HeaplessJsonCodec::<HeaplessMessage, 64>::new();
+    let sent = HeaplessMessage {
+        id: 7,
+        method: heapless::String::from("pong"),
+    };
+    let mut buf = BytesMut::new();
+    codec.encode(sent.clone(), &mut buf).unwrap();
+    assert_eq!(b"{\"id\":7,\"method\":\"pong\"}\n", &buf[..]);
+
+    assert_eq!(Some(sent), codec.decode(&mut buf).unwrap());
+}
+
+#[test]
+fn test_heapless_json_buffer_too_small() {
+    let mut codec = HeaplessJsonCodec::<HeaplessMessage, 8>::new();
+    let message = HeaplessMessage {
+        id: 7,
+        method: heapless::String::from("pong"),
+    };
+    assert!(matches!(
+        codec.encode(message, &mut BytesMut::new()),
+        Err(IoError::FrameTooLong)
+    ));
+}
+
+#[tokio::test]
+async fn test_framed_json_messages() {
+    let (a, b) = duplex(64);
+    let mut a = JsonCodec::<Message>::new().framed(a);
+    let mut b = JsonCodec::<Message>::new().framed(b);
+
+    a.send(message(1)).await.unwrap();
+    a.send(message(2)).await.unwrap();
+
+    assert_eq!(message(1), b.next().await.unwrap().unwrap());
+    assert_eq!(message(2), b.next().await.unwrap().unwrap());
+}
+
+#[tokio::test]
+async fn test_framed_json_skips_invalid_messages() {
+    let (mut a, b) = duplex(64);
+    let mut b = JsonCodec::<Message>::new().framed(b);
+
+    a.write_all(b"not json\n{\"id\":1,\"method\":\"ping\\n\"}\n")
+        .await
+        .unwrap();
+    a.shutdown().await.unwrap();
+
+    assert!(b.next().await.unwrap().is_err());
+    assert_eq!(message(1), b.next().await.unwrap().unwrap());
+    assert!(b.next().await.is_none());
+}
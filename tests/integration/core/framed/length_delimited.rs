@@ -0,0 +1,83 @@
+use bytes::BytesMut;
+use em_as_net::core::framed::codec::{Decoder, Encoder, LengthDelimitedCodec};
+use em_as_net::core::framed::IoError;
+use em_as_net::core::io::duplex;
+use futures::{SinkExt, StreamExt};
+
+#[test]
+fn test_length_delimited_decodes_partial_frames() {
+    let mut codec = LengthDelimitedCodec::new();
+    let mut buf = BytesMut::from(&b"\x00\x00\x00\x05hel"[..]);
+
+    assert_eq!(None, codec.decode(&mut buf).unwrap());
+    buf.extend_from_slice(b"lo\x00\x00\x00\x00\x00\x00");
+    assert_eq!(b"hello", &codec.decode(&mut buf).unwrap().unwrap()[..]);
+    assert_eq!(b"", &codec.decode(&mut buf).unwrap().unwrap()[..]);
+    assert_eq!(None, codec.decode(&mut buf).unwrap());
+    assert_eq!(b"\x00\x00", &buf[..]);
+}
+
+#[test]
+fn test_length_delimited_builder_options() {
+    // 1 byte of magic and a 2 byte little endian payload length, with the header kept in the
+    // frame.
+    let mut codec = LengthDelimitedCodec::builder()
+        .length_field_offset(1)
+        .length_field_length(2)
+        .little_endian()
+        .length_adjustment(3)
+        .num_skip(0)
+        .new_codec();
+    let mut buf = BytesMut::from(&b"\xca\x02\x00hi"[..]);
+    assert_eq!(
+        b"\xca\x02\x00hi",
+        &codec.decode(&mut buf).unwrap().unwrap()[..]
+    );
+
+    let mut codec = LengthDelimitedCodec::builder()
+        .length_field_length(8)
+        .little_endian()
+        .new_codec();
+    let mut buf = BytesMut::new();
+    codec.encode(&b"abc"[..], &mut buf).unwrap();
+    assert_eq!(b"\x03\x00\x00\x00\x00\x00\x00\x00abc", &buf[..]);
+    assert_eq!(b"abc", &codec.decode(&mut buf).unwrap().unwrap()[..]);
+}
+
+#[test]
+fn test_length_delimited_max_frame_length() {
+    let mut codec = LengthDelimitedCodec::builder()
+        .max_frame_length(4)
+        .new_codec();
+    let mut buf = BytesMut::from(&b"\x00\x00\x00\x05hello"[..]);
+    assert!(matches!(codec.decode(&mut buf), Err(IoError::FrameTooLong)));
+    assert!(matches!(
+        codec.encode(&b"hello"[..], &mut BytesMut::new()),
+        Err(IoError::FrameTooLong)
+    ));
+
+    // The length field can't hold 256.
+    let mut codec = LengthDelimitedCodec::builder()
+        .length_field_length(1)
+        .new_codec();
+    assert!(matches!(
+        codec.encode(&[0u8; 256][..], &mut BytesMut::new()),
+        Err(IoError::FrameTooLong)
+    ));
+}
+
+#[tokio::test]
+async fn test_length_delimited_framed() {
+    let (a, b) = duplex(64);
+    let mut a = LengthDelimitedCodec::builder()
+        .length_field_length(2)
+        .new_framed(a);
+    let mut b = LengthDelimitedCodec::builder()
+        .length_field_length(2)
+        .new_framed(b);
+
+    a.send(&b"first"[..]).await.unwrap();
+    a.send(&b"second"[..]).await.unwrap();
+    assert_eq!(b"first", &b.next().await.unwrap().unwrap()[..]);
+    assert_eq!(b"second", &b.next().await.unwrap().unwrap()[..]);
+}
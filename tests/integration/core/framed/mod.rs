@@ -0,0 +1,6 @@
+mod delimiters;
+mod hdlc;
+#[cfg(feature = "json")]
+mod json;
+mod length_delimited;
+mod stuffing;
@@ -0,0 +1,125 @@
+use bytes::BytesMut;
+use em_as_net::core::framed::codec::{CobsCodec, Decoder, Encoder, SlipCodec};
+use em_as_net::core::framed::IoError;
+use em_as_net::core::io::{duplex, AsyncWriteExt};
+use futures::StreamExt;
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+/// Returns a payload of `1..=max_len` bytes, rich in the bytes the codecs have to stuff.
+fn random_payload(rng: &mut impl RngCore, max_len: usize) -> Vec<u8> {
+    let len = rng.gen_range(1..=max_len);
+    (0..len)
+        .map(|_| match rng.gen_range(0..4) {
+            0 => [0x00, 0xc0, 0xdb, 0xdc, 0xdd, 0xff][rng.gen_range(0..6)],
+            _ => rng.gen(),
+        })
+        .collect()
+}
+
+/// Returns the seed of the random payloads. Set `ROUND_TRIP_SEED` to the seed printed by a
+/// failed test to reproduce it.
+fn seed() -> u64 {
+    match std::env::var("ROUND_TRIP_SEED") {
+        Ok(seed) => seed.parse().unwrap(),
+        Err(_) => rand::random(),
+    }
+}
+
+/// Encodes random payloads back to back and decodes them from randomly sized chunks.
+fn round_trip<C>(mut codec: C)
+where
+    C: Decoder<Item = BytesMut, Error = IoError> + for<'a> Encoder<&'a [u8], Error = IoError>,
+{
+    let seed = seed();
+    // Captured output is only shown if the test fails.
+    println!("ROUND_TRIP_SEED={seed}");
+    let mut rng = StdRng::seed_from_u64(seed);
+    for _ in 0..200 {
+        let payloads: Vec<Vec<u8>> = (0..rng.gen_range(1..8))
+            .map(|_| random_payload(&mut rng, 600))
+            .collect();
+        let mut encoded = BytesMut::new();
+        for payload in &payloads {
+            codec.encode(&payload[..], &mut encoded).unwrap();
+        }
+
+        let mut buf = BytesMut::new();
+        let mut decoded = Vec::new();
+        while !encoded.is_empty() {
+            let chunk = rng.gen_range(1..=encoded.len());
+            buf.extend_from_slice(&encoded.split_to(chunk));
+            while let Some(frame) = codec.decode(&mut buf).unwrap() {
+                decoded.push(frame.to_vec());
+            }
+        }
+        assert_eq!(payloads, decoded, "ROUND_TRIP_SEED={seed}");
+        assert!(buf.is_empty(), "ROUND_TRIP_SEED={seed}");
+    }
+}
+
+#[test]
+fn test_slip_round_trip() {
+    round_trip(SlipCodec::new());
+}
+
+#[test]
+fn test_cobs_round_trip() {
+    round_trip(CobsCodec::new());
+}
+
+#[test]
+fn test_cobs_encoding() {
+    let mut codec = CobsCodec::new();
+    let mut buf = BytesMut::new();
+    codec
+        .encode(&[0x11, 0x00, 0x00, 0x22][..], &mut buf)
+        .unwrap();
+    assert_eq!(b"\x02\x11\x01\x03\x22\x00", &buf[..]);
+
+    let payload: Vec<u8> = (1..=254).collect();
+    let mut buf = BytesMut::new();
+    codec.encode(&payload[..], &mut buf).unwrap();
+    assert_eq!(256, buf.len());
+    assert_eq!(0xff, buf[0]);
+    assert_eq!(payload, codec.decode(&mut buf).unwrap().unwrap());
+}
+
+#[test]
+fn test_slip_recovers_from_corrupt_frames() {
+    let mut codec = SlipCodec::new_with_max_length(4);
+    // An invalid escape, a too long frame and a valid one.
+    let mut buf = BytesMut::from(&b"\xc0\xdb\x01\xc0\xc0hello\xc0\xc0ok\xc0"[..]);
+
+    assert!(matches!(codec.decode(&mut buf), Err(IoError::InvalidFrame)));
+    let error = codec.decode(&mut buf).unwrap_err();
+    assert!(matches!(error, IoError::FrameTooLong));
+    assert!(codec.is_recoverable(&error));
+    assert_eq!(b"ok", &codec.decode(&mut buf).unwrap().unwrap()[..]);
+}
+
+#[test]
+fn test_cobs_max_frame_length() {
+    let mut codec = CobsCodec::new_with_max_length(2);
+    assert!(matches!(
+        codec.encode(&b"abc"[..], &mut BytesMut::new()),
+        Err(IoError::FrameTooLong)
+    ));
+
+    // The first code points past the end of the frame.
+    let mut buf = BytesMut::from(&b"\x05ab\x00\x03ab\x00"[..]);
+    assert!(matches!(codec.decode(&mut buf), Err(IoError::InvalidFrame)));
+    assert_eq!(b"ab", &codec.decode(&mut buf).unwrap().unwrap()[..]);
+}
+
+#[tokio::test]
+async fn test_framed_skips_recoverable_errors() {
+    let (mut a, b) = duplex(64);
+    let mut b = CobsCodec::new().framed(b);
+
+    a.write_all(b"\x05ab\x00\x03ab\x00").await.unwrap();
+    a.shutdown().await.unwrap();
+    assert!(b.next().await.unwrap().is_err());
+    assert_eq!(b"ab", &b.next().await.unwrap().unwrap()[..]);
+    assert!(b.next().await.is_none());
+}
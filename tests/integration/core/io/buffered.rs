@@ -0,0 +1,52 @@
+use em_as_net::core::io::{
+    AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, BufWriter, TokioCompat,
+};
+
+#[tokio::test]
+async fn test_buf_reader_read_line_with_caller_buffer() {
+    let mut buffer = [0u8; 8];
+    let mut reader = BufReader::with_buffer(
+        TokioCompat::new(&b"hello\r\nworld\nlast"[..]),
+        &mut buffer[..],
+    );
+
+    let mut line = String::new();
+    assert_eq!(7, reader.read_line(&mut line).await.unwrap());
+    assert_eq!("hello\r\n", line);
+
+    line.clear();
+    assert_eq!(6, reader.read_line(&mut line).await.unwrap());
+    assert_eq!("world\n", line);
+
+    line.clear();
+    assert_eq!(4, reader.read_line(&mut line).await.unwrap());
+    assert_eq!("last", line);
+
+    assert_eq!(0, reader.read_line(&mut line).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_buf_reader_read_until() {
+    let mut reader = BufReader::new(TokioCompat::new(&b"a,b,c"[..]));
+
+    let mut field = Vec::new();
+    reader.read_until(b',', &mut field).await.unwrap();
+    assert_eq!(b"a,", &field[..]);
+
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).await.unwrap();
+    assert_eq!(b"b,c", &rest[..]);
+}
+
+#[tokio::test]
+async fn test_buf_writer_flushes_on_demand() {
+    let mut writer = BufWriter::with_capacity(16, TokioCompat::new(Vec::<u8>::new()));
+
+    writer.write_all(b"hello").await.unwrap();
+    assert!(writer.get_ref().get_ref().is_empty());
+    assert_eq!(b"hello", writer.buffer());
+
+    writer.flush().await.unwrap();
+    assert_eq!(b"hello", &writer.get_ref().get_ref()[..]);
+    assert!(writer.buffer().is_empty());
+}
@@ -0,0 +1,35 @@
+use em_as_net::core::framed::{Codec, Framed};
+use em_as_net::core::io::{duplex, AsyncReadExt, AsyncWriteExt, Counted, TokioCompat};
+use futures::{SinkExt, StreamExt};
+
+#[tokio::test]
+async fn test_take_and_chain() {
+    let header = TokioCompat::new(&b"HEADER-and-more"[..]).take(6);
+    let mut reader = header.chain(TokioCompat::new(&b":body"[..]));
+
+    let mut received = Vec::new();
+    reader.read_to_end(&mut received).await.unwrap();
+    assert_eq!(b"HEADER:body", &received[..]);
+}
+
+#[tokio::test]
+async fn test_limit_rejects_writes_past_the_limit() {
+    let mut writer = TokioCompat::new(Vec::new()).limit(4);
+
+    assert_eq!(4, writer.write(b"123456").await.unwrap());
+    assert!(writer.write_all(b"7").await.is_err());
+    assert_eq!(b"1234", &writer.get_ref().get_ref()[..]);
+}
+
+#[tokio::test]
+async fn test_counted_under_framed() {
+    let (a, b) = duplex(64);
+    let mut a = Framed::new(Counted::new(a), Codec::new());
+    let mut b = Framed::new(Counted::new(b), Codec::new());
+
+    a.send(&b"ping"[..]).await.unwrap();
+    assert_eq!(b"ping", &b.next().await.unwrap().unwrap()[..]);
+
+    assert_eq!(4, a.get_ref().bytes_written());
+    assert_eq!(4, b.get_ref().bytes_read());
+}
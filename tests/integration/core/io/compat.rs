@@ -0,0 +1,75 @@
+use em_as_net::core::framed::codec::LinesCodec;
+use em_as_net::core::framed::Framed;
+use em_as_net::core::io::{duplex, AsyncReadExt, AsyncWriteExt, FuturesCompat, TokioCompat};
+use futures::io::{AsyncReadExt as _, AsyncWriteExt as _, Cursor};
+use futures::{SinkExt, StreamExt};
+
+#[tokio::test]
+async fn test_futures_compat_framed_over_futures_io() {
+    let reader = FuturesCompat::new(Cursor::new(b"one\ntwo\n".to_vec()));
+    let mut framed = Framed::new(reader, LinesCodec::new());
+    assert_eq!("one", framed.next().await.unwrap().unwrap());
+    assert_eq!("two", framed.next().await.unwrap().unwrap());
+    assert!(framed.next().await.is_none());
+
+    let writer = FuturesCompat::new(Cursor::new(Vec::new()));
+    let mut framed = Framed::new(writer, LinesCodec::new());
+    framed.send("three".to_string()).await.unwrap();
+    assert_eq!(
+        b"three\n",
+        &framed.into_inner().into_inner().into_inner()[..]
+    );
+}
+
+#[tokio::test]
+async fn test_futures_compat_drives_our_streams() {
+    let (a, b) = duplex(4);
+    let mut a = FuturesCompat::new(a);
+    let mut b = FuturesCompat::new(b);
+
+    let writer = tokio::spawn(async move {
+        a.write_all(b"hello world").await.unwrap();
+        a.close().await.unwrap();
+    });
+
+    let mut received = Vec::new();
+    b.read_to_end(&mut received).await.unwrap();
+    assert_eq!(b"hello world", &received[..]);
+    writer.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_tokio_compat_drives_our_streams() {
+    let (a, b) = duplex(4);
+    let mut a = TokioCompat::new(a);
+    let mut b = TokioCompat::new(b);
+
+    let writer = tokio::spawn(async move {
+        let mut source: &[u8] = b"hello world";
+        let copied = tokio::io::copy(&mut source, &mut a).await.unwrap();
+        tokio::io::AsyncWriteExt::shutdown(&mut a).await.unwrap();
+        copied
+    });
+
+    let mut received = Vec::new();
+    tokio::io::copy(&mut b, &mut received).await.unwrap();
+    assert_eq!(b"hello world", &received[..]);
+    assert_eq!(11, writer.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_tokio_compat_wraps_tokio_io() {
+    let (a, b) = tokio::io::duplex(4);
+    let mut a = TokioCompat::new(a);
+    let mut b = TokioCompat::new(b);
+
+    let writer = tokio::spawn(async move {
+        a.write_all(b"hello world").await.unwrap();
+        a.shutdown().await.unwrap();
+    });
+
+    let mut received = Vec::new();
+    b.read_to_end(&mut received).await.unwrap();
+    assert_eq!(b"hello world", &received[..]);
+    writer.await.unwrap();
+}
@@ -0,0 +1,54 @@
+use em_as_net::core::io::{copy, copy_bidirectional, duplex, AsyncReadExt, AsyncWriteExt};
+
+#[tokio::test]
+async fn test_copy_until_eof() {
+    let (mut source, mut reader) = duplex(16);
+    let (mut writer, mut sink) = duplex(16);
+
+    let producer = tokio::spawn(async move {
+        source.write_all(b"some bytes to forward").await.unwrap();
+        source.shutdown().await.unwrap();
+        source
+    });
+    let consumer = tokio::spawn(async move {
+        let mut received = Vec::new();
+        sink.read_to_end(&mut received).await.unwrap();
+        received
+    });
+
+    let mut buf = [0u8; 4];
+    let copied = copy(&mut reader, &mut writer, &mut buf).await.unwrap();
+    assert_eq!(21, copied);
+    drop(writer);
+
+    assert_eq!(b"some bytes to forward", &consumer.await.unwrap()[..]);
+    let _source = producer.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_copy_bidirectional_propagates_half_close() {
+    let (mut client, mut proxy_a) = duplex(32);
+    let (mut proxy_b, mut server) = duplex(32);
+
+    let proxy = tokio::spawn(async move {
+        let mut buf_a = [0u8; 8];
+        let mut buf_b = [0u8; 8];
+        copy_bidirectional(&mut proxy_a, &mut proxy_b, &mut buf_a, &mut buf_b).await
+    });
+
+    client.write_all(b"request").await.unwrap();
+    client.shutdown().await.unwrap();
+
+    // The server sees EOF after the request and still answers on its open half.
+    let mut request = Vec::new();
+    server.read_to_end(&mut request).await.unwrap();
+    assert_eq!(b"request", &request[..]);
+    server.write_all(b"response").await.unwrap();
+    server.shutdown().await.unwrap();
+
+    let mut response = Vec::new();
+    client.read_to_end(&mut response).await.unwrap();
+    assert_eq!(b"response", &response[..]);
+
+    assert_eq!((7, 8), proxy.await.unwrap().unwrap());
+}
@@ -0,0 +1,44 @@
+use em_as_net::core::framed::{Codec, Framed};
+use em_as_net::core::io::{duplex, duplex_fixed, AsyncReadExt, AsyncWriteExt};
+use futures::{SinkExt, StreamExt};
+
+#[tokio::test]
+async fn test_duplex_write_blocks_until_peer_reads() {
+    let (mut a, mut b) = duplex(4);
+
+    let writer = tokio::spawn(async move {
+        a.write_all(b"hello world").await.unwrap();
+        a.shutdown().await.unwrap();
+        a
+    });
+
+    let mut received = Vec::new();
+    b.read_to_end(&mut received).await.unwrap();
+    assert_eq!(b"hello world", &received[..]);
+
+    let _a = writer.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_duplex_fixed_drop_closes_both_directions() {
+    let (mut a, mut b) = duplex_fixed::<16>();
+
+    a.write_all(b"bye").await.unwrap();
+    drop(a);
+
+    let mut received = Vec::new();
+    b.read_to_end(&mut received).await.unwrap();
+    assert_eq!(b"bye", &received[..]);
+    assert!(b.write_all(b"anyone?").await.is_err());
+}
+
+#[tokio::test]
+async fn test_duplex_framed() {
+    let (a, b) = duplex(64);
+    let mut a = Framed::new(a, Codec::new());
+    let mut b = Framed::new(b, Codec::new());
+
+    a.send(&b"ping"[..]).await.unwrap();
+    let frame = b.next().await.unwrap().unwrap();
+    assert_eq!(b"ping", &frame[..]);
+}
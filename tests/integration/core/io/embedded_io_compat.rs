@@ -0,0 +1,147 @@
+use em_as_net::core::framed::IoError;
+use em_as_net::core::io::{
+    duplex, AsyncReadExt, AsyncWriteExt, CancelSafe, FromEmbeddedIo, ToEmbeddedIo,
+};
+use embedded_io::asynch::{Read, Write};
+use embedded_io::{ErrorKind, Io};
+use std::collections::VecDeque;
+use std::future::poll_fn;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+use std::time::Duration;
+
+#[derive(Default)]
+struct State {
+    bytes: VecDeque<u8>,
+    waker: Option<Waker>,
+    is_closed: bool,
+}
+
+/// An in-memory `embedded_io` transport. Reads wait for bytes written through a clone.
+#[derive(Clone, Default)]
+struct Pipe(Arc<Mutex<State>>);
+
+impl Pipe {
+    fn close(&self) {
+        let mut state = self.0.lock().unwrap();
+        state.is_closed = true;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Io for Pipe {
+    type Error = IoError;
+}
+
+impl Read for Pipe {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        poll_fn(|cx| {
+            let mut state = self.0.lock().unwrap();
+            if state.bytes.is_empty() && !state.is_closed {
+                state.waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+            let n = buf.len().min(state.bytes.len());
+            for (slot, byte) in buf.iter_mut().zip(state.bytes.drain(..n)) {
+                *slot = byte;
+            }
+
+            Poll::Ready(Ok(n))
+        })
+        .await
+    }
+}
+
+impl Write for Pipe {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        let mut state = self.0.lock().unwrap();
+        if state.is_closed {
+            return Err(IoError::Io(ErrorKind::Other));
+        }
+        state.bytes.extend(buf);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+
+        Ok(buf.len())
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl CancelSafe for Pipe {}
+
+#[tokio::test]
+async fn test_from_embedded_io_round_trip() {
+    let pipe = Pipe::default();
+    let mut writer = FromEmbeddedIo::new(pipe.clone());
+    let mut reader = FromEmbeddedIo::new(pipe);
+
+    let mut buf = [0u8; 11];
+    let (written, read) = tokio::join!(
+        async {
+            // The reader polls first and has to be woken up.
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            writer.write_all(b"hello ").await?;
+            writer.write_all(b"world").await?;
+            writer.flush().await
+        },
+        reader.read_exact(&mut buf),
+    );
+    written.unwrap();
+    assert_eq!(11, read.unwrap());
+    assert_eq!(b"hello world", &buf);
+
+    writer.get_ref().close();
+    assert_eq!(0, reader.read(&mut buf).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_from_embedded_io_error() {
+    let pipe = Pipe::default();
+    pipe.close();
+
+    let error = FromEmbeddedIo::new(pipe).write(b"hello").await.unwrap_err();
+    assert_eq!(IoError::Io(ErrorKind::Other).to_string(), error.to_string());
+}
+
+#[tokio::test]
+async fn test_to_embedded_io_round_trip() {
+    let (a, b) = duplex(64);
+    let mut a = ToEmbeddedIo::new(a);
+    let mut b = ToEmbeddedIo::new(b);
+
+    a.write_all(b"hello").await.unwrap();
+    a.flush().await.unwrap();
+
+    let mut buf = [0u8; 8];
+    let n = b.read(&mut buf).await.unwrap();
+    assert_eq!(b"hello", &buf[..n]);
+}
+
+#[tokio::test]
+async fn test_to_embedded_io_keeps_error_cause() {
+    let (a, b) = duplex(64);
+    drop(b);
+
+    let error = ToEmbeddedIo::new(a).write(b"hello").await.unwrap_err();
+    assert!(matches!(
+        error,
+        IoError::WriteFailed(cause) if cause == IoError::BrokenPipe.to_string()
+    ));
+}
+
+#[tokio::test]
+async fn test_embedded_io_compat_round_trip() {
+    let (a, mut b) = duplex(64);
+    let mut a = FromEmbeddedIo::new(ToEmbeddedIo::new(a));
+
+    a.write_all(b"hello").await.unwrap();
+    let mut buf = [0u8; 5];
+    b.read_exact(&mut buf).await.unwrap();
+    assert_eq!(b"hello", &buf);
+}
@@ -0,0 +1,87 @@
+use em_as_net::core::framed::IoError;
+use em_as_net::core::io::{duplex, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A writer that stopped accepting bytes without failing.
+struct Full;
+
+impl AsyncWrite for Full {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        _buf: &[u8],
+    ) -> Poll<anyhow::Result<usize>> {
+        Poll::Ready(Ok(0))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<anyhow::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<anyhow::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn test_read_and_write() {
+    let (mut a, mut b) = duplex(64);
+    assert_eq!(5, a.write(b"hello").await.unwrap());
+    a.flush().await.unwrap();
+
+    let mut buf = [0u8; 8];
+    assert_eq!(5, b.read(&mut buf).await.unwrap());
+    assert_eq!(b"hello", &buf[..5]);
+}
+
+#[tokio::test]
+async fn test_read_exact() {
+    let (mut a, mut b) = duplex(64);
+    a.write_all(b"hello world").await.unwrap();
+
+    let mut buf = [0u8; 5];
+    assert_eq!(5, b.read_exact(&mut buf).await.unwrap());
+    assert_eq!(b"hello", &buf);
+}
+
+#[tokio::test]
+async fn test_read_exact_unexpected_eof() {
+    let (mut a, mut b) = duplex(64);
+    a.write_all(b"hel").await.unwrap();
+    a.shutdown().await.unwrap();
+
+    let error = b.read_exact(&mut [0u8; 5]).await.unwrap_err();
+    assert_eq!(IoError::UnexpectedEof.to_string(), error.to_string());
+}
+
+#[tokio::test]
+async fn test_read_to_end() {
+    let (mut a, mut b) = duplex(4);
+    let writer = tokio::spawn(async move {
+        a.write_all(b"more than the pipe holds").await.unwrap();
+        a.shutdown().await.unwrap();
+    });
+
+    let mut buf = b"> ".to_vec();
+    assert_eq!(24, b.read_to_end(&mut buf).await.unwrap());
+    assert_eq!(b"> more than the pipe holds", &buf[..]);
+    writer.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_write_all_write_zero() {
+    let error = Full.write_all(b"hello").await.unwrap_err();
+    assert_eq!(IoError::WriteZero.to_string(), error.to_string());
+
+    // Nothing to write never touches the writer.
+    Full.write_all(b"").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_shutdown_signals_eof() {
+    let (mut a, mut b) = duplex(64);
+    a.shutdown().await.unwrap();
+
+    assert_eq!(0, b.read(&mut [0u8; 8]).await.unwrap());
+}
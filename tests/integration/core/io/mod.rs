@@ -0,0 +1,12 @@
+mod buffered;
+mod combinators;
+mod compat;
+mod copy;
+mod duplex;
+mod embedded_io_compat;
+mod ext;
+mod reconnect;
+mod split;
+mod throttle;
+mod timeout;
+mod vectored;
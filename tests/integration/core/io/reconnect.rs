@@ -0,0 +1,187 @@
+use anyhow::anyhow;
+use em_as_net::core::framed::IoError;
+use em_as_net::core::io::{
+    duplex, AsyncReadExt, AsyncWriteExt, Backoff, ConnectionEvent, DuplexStream, Reconnecting,
+    WritePolicy,
+};
+use embassy_time::Duration;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+/// Returns a connector failing the first `failures` attempts, and the peers of the streams
+/// it creates.
+fn connector(
+    failures: u32,
+) -> (
+    impl FnMut() -> std::future::Ready<anyhow::Result<DuplexStream>> + Unpin,
+    UnboundedReceiver<DuplexStream>,
+) {
+    let (tx, rx) = unbounded_channel();
+    let attempts = AtomicU32::new(0);
+    let connect = move || {
+        if attempts.fetch_add(1, Ordering::Relaxed) < failures {
+            return std::future::ready(Err(anyhow!("connection refused")));
+        }
+        let (local, peer) = duplex(64);
+        tx.send(peer).unwrap();
+        std::future::ready(Ok(local))
+    };
+
+    (connect, rx)
+}
+
+fn backoff() -> Backoff {
+    Backoff::new()
+        .initial(Duration::from_millis(1))
+        .max(Duration::from_millis(4))
+}
+
+#[tokio::test]
+async fn test_reconnecting_retries_with_backoff() {
+    let (connect, mut peers) = connector(3);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    let mut stream = Reconnecting::new(connect, backoff().jitter(false), WritePolicy::FailFast)
+        .on_event(move |event| recorded.lock().unwrap().push(event));
+
+    stream.connect().await.unwrap();
+    assert!(stream.is_connected());
+    assert_eq!(
+        vec![
+            ConnectionEvent::ConnectFailed {
+                attempt: 1,
+                retry_in: Duration::from_millis(1)
+            },
+            ConnectionEvent::ConnectFailed {
+                attempt: 2,
+                retry_in: Duration::from_millis(2)
+            },
+            ConnectionEvent::ConnectFailed {
+                attempt: 3,
+                retry_in: Duration::from_millis(4)
+            },
+            ConnectionEvent::Connected,
+        ],
+        *events.lock().unwrap()
+    );
+
+    let mut peer = peers.recv().await.unwrap();
+    stream.write_all(b"ping").await.unwrap();
+    let mut buf = [0; 4];
+    peer.read_exact(&mut buf).await.unwrap();
+    assert_eq!(b"ping", &buf);
+}
+
+#[tokio::test]
+async fn test_reconnecting_reconnects_after_peer_closed() {
+    let (connect, mut peers) = connector(0);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    let mut stream = Reconnecting::new(connect, backoff(), WritePolicy::FailFast)
+        .on_event(move |event| recorded.lock().unwrap().push(event));
+    stream.connect().await.unwrap();
+
+    drop(peers.recv().await.unwrap());
+    let reader = tokio::spawn(async move {
+        let mut buf = [0; 5];
+        stream.read_exact(&mut buf).await.unwrap();
+        buf
+    });
+    let mut peer = peers.recv().await.unwrap();
+    peer.write_all(b"hello").await.unwrap();
+
+    assert_eq!(b"hello", &reader.await.unwrap());
+    assert_eq!(
+        vec![
+            ConnectionEvent::Connected,
+            ConnectionEvent::Disconnected,
+            ConnectionEvent::Connected,
+        ],
+        *events.lock().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_reconnecting_write_policies() {
+    // Connects once, every reconnect fails.
+    let (tx, mut peers) = unbounded_channel();
+    let attempts = AtomicU32::new(0);
+    let connect = move || {
+        if attempts.fetch_add(1, Ordering::Relaxed) > 0 {
+            return std::future::ready(Err(anyhow!("connection refused")));
+        }
+        let (local, peer) = duplex(64);
+        tx.send(peer).unwrap();
+        std::future::ready(Ok(local))
+    };
+    let mut stream = Reconnecting::new(connect, backoff(), WritePolicy::FailFast);
+    stream.connect().await.unwrap();
+    drop(peers.recv().await.unwrap());
+    assert!(stream.write(b"lost").await.is_err());
+    assert_eq!(
+        IoError::WriteNotConnected.to_string(),
+        stream.write(b"lost").await.unwrap_err().to_string()
+    );
+
+    let (connect, mut peers) = connector(2);
+    let mut stream = Reconnecting::new(connect, backoff(), WritePolicy::Buffer(8));
+    assert_eq!(8, stream.write(b"buffered bytes").await.unwrap());
+    stream.flush().await.unwrap();
+
+    let mut peer = peers.recv().await.unwrap();
+    let mut buf = [0; 8];
+    peer.read_exact(&mut buf).await.unwrap();
+    assert_eq!(b"buffered", &buf);
+}
+
+#[tokio::test]
+async fn test_reconnecting_fail_fast_waits_for_first_connection() {
+    let (connect, mut peers) = connector(2);
+    let mut stream = Reconnecting::new(connect, backoff(), WritePolicy::FailFast);
+    stream.write_all(b"ping").await.unwrap();
+    stream.flush().await.unwrap();
+
+    let mut peer = peers.recv().await.unwrap();
+    let mut buf = [0; 4];
+    peer.read_exact(&mut buf).await.unwrap();
+    assert_eq!(b"ping", &buf);
+}
+
+#[tokio::test]
+async fn test_reconnecting_shutdown_delivers_buffered_bytes() {
+    let (connect, mut peers) = connector(2);
+    let mut stream = Reconnecting::new(connect, backoff(), WritePolicy::Buffer(8));
+    assert_eq!(8, stream.write(b"buffered").await.unwrap());
+    stream.shutdown().await.unwrap();
+
+    let mut peer = peers.recv().await.unwrap();
+    let mut received = Vec::new();
+    peer.read_to_end(&mut received).await.unwrap();
+    assert_eq!(b"buffered", &received[..]);
+}
+
+#[tokio::test]
+async fn test_reconnecting_shutdown_fails_with_undeliverable_bytes() {
+    let (connect, _peers) = connector(u32::MAX);
+    let mut stream = Reconnecting::new(connect, backoff().max_attempts(2), WritePolicy::Buffer(8));
+    assert_eq!(8, stream.write(b"buffered").await.unwrap());
+
+    assert!(stream.shutdown().await.is_err());
+}
+
+#[tokio::test]
+async fn test_reconnecting_gives_up() {
+    let (connect, _peers) = connector(u32::MAX);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    let mut stream = Reconnecting::new(connect, backoff().max_attempts(2), WritePolicy::FailFast)
+        .on_event(move |event| recorded.lock().unwrap().push(event));
+
+    assert!(stream.connect().await.is_err());
+    assert!(stream.read(&mut [0; 4]).await.is_err());
+    assert_eq!(
+        Some(&ConnectionEvent::GaveUp),
+        events.lock().unwrap().last()
+    );
+}
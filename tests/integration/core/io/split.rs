@@ -0,0 +1,53 @@
+use em_as_net::core::io::{split, AsyncReadExt, AsyncWriteExt, TokioCompat};
+use em_as_net::core::tcp::adapters::TcpAdapterTokio;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::net::TcpListener;
+
+#[tokio::test]
+async fn test_split_read_and_write_from_different_tasks() {
+    let (client, mut server) = tokio::io::duplex(64);
+    let (mut read_half, mut write_half) = split(TokioCompat::new(client));
+
+    let writer = tokio::spawn(async move {
+        write_half.write_all(b"ping").await.unwrap();
+        write_half
+    });
+    let reader = tokio::spawn(async move {
+        let mut buf = [0u8; 4];
+        read_half.read_exact(&mut buf).await.unwrap();
+        assert_eq!(b"pong", &buf);
+        read_half
+    });
+
+    let mut buf = [0u8; 4];
+    server.read_exact(&mut buf).await.unwrap();
+    assert_eq!(b"ping", &buf);
+    server.write_all(b"pong").await.unwrap();
+
+    let write_half = writer.await.unwrap();
+    let read_half = reader.await.unwrap();
+    assert!(read_half.is_pair_of(&write_half));
+    let _client = read_half.unsplit(write_half);
+}
+
+#[tokio::test]
+async fn test_tcp_adapter_tokio_into_split() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 5];
+        stream.read_exact(&mut buf).await.unwrap();
+        stream.write_all(&buf).await.unwrap();
+    });
+
+    let adapter = TcpAdapterTokio::connect(addr).await.unwrap();
+    let (mut read_half, mut write_half) = adapter.into_split();
+
+    write_half.write_all(b"hello").await.unwrap();
+    let mut buf = [0u8; 5];
+    read_half.read_exact(&mut buf).await.unwrap();
+    assert_eq!(b"hello", &buf);
+
+    TcpAdapterTokio::unsplit(read_half, write_half).unwrap();
+}
@@ -0,0 +1,36 @@
+use em_as_net::core::io::{duplex, AsyncReadExt, AsyncWriteExt, Rate, Throttled};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn test_throttled_write_rate() {
+    let (a, mut b) = duplex(1024);
+    let mut a = Throttled::new(a, None, Some(Rate::new(100).burst(10)));
+
+    let reader = tokio::spawn(async move {
+        let mut received = [0u8; 30];
+        b.read_exact(&mut received).await.unwrap();
+    });
+
+    // The burst goes out right away, the remaining 20 bytes take about 200ms.
+    let start = Instant::now();
+    a.write_all(&[0u8; 30]).await.unwrap();
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(150), "{elapsed:?}");
+    assert!(elapsed < Duration::from_secs(2), "{elapsed:?}");
+
+    reader.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_throttled_rate_can_be_lifted() {
+    let (mut a, b) = duplex(1024);
+    let mut b = Throttled::new(b, Some(Rate::new(1).burst(1)), None);
+
+    a.write_all(b"hello").await.unwrap();
+    let mut buf = [0u8; 5];
+    assert_eq!(1, b.read(&mut buf).await.unwrap());
+
+    b.set_read_rate(None);
+    b.read_exact(&mut buf[1..]).await.unwrap();
+    assert_eq!(b"hello", &buf);
+}
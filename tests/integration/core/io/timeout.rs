@@ -0,0 +1,69 @@
+use em_as_net::core::framed::IoError;
+use em_as_net::core::io::timeout::Duration;
+use em_as_net::core::io::{duplex, AsyncReadExt, AsyncWriteExt, Timeout, Timeouts};
+use std::future::pending;
+
+#[tokio::test]
+async fn test_read_timed_out() {
+    let (_a, b) = duplex(64);
+    let mut b = Timeout::new(b, Timeouts::new().read(Duration::from_millis(50)));
+
+    let error = b.read(&mut [0u8; 8]).await.unwrap_err();
+    assert_eq!(IoError::ReadTimedOut.to_string(), error.to_string());
+}
+
+#[tokio::test]
+async fn test_write_timed_out() {
+    let (a, _b) = duplex(4);
+    let mut a = Timeout::new(a, Timeouts::new().write(Duration::from_millis(50)));
+
+    // The first 4 bytes fit into the pipe, the rest waits for a reader that never comes.
+    let error = a.write_all(b"deadline").await.unwrap_err();
+    assert_eq!(IoError::WriteTimedOut.to_string(), error.to_string());
+}
+
+#[tokio::test]
+async fn test_idle_timed_out() {
+    let (_a, b) = duplex(64);
+    let mut b = Timeout::new(b, Timeouts::new().idle(Duration::from_millis(50)));
+
+    let error = b.read(&mut [0u8; 8]).await.unwrap_err();
+    assert_eq!(IoError::IdleTimedOut.to_string(), error.to_string());
+}
+
+#[tokio::test]
+async fn test_idle_deadline_resets_on_progress() {
+    let (mut a, b) = duplex(64);
+    let mut b = Timeout::new(b, Timeouts::new().idle(Duration::from_millis(150)));
+
+    let writer = tokio::spawn(async move {
+        for byte in 0..5u8 {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            a.write_all(&[byte]).await.unwrap();
+        }
+        a
+    });
+
+    // Reading takes about 250ms in total, longer than the idle deadline, but every byte
+    // arrives within it.
+    let mut buf = [0u8; 1];
+    for byte in 0..5u8 {
+        b.read_exact(&mut buf).await.unwrap();
+        assert_eq!(byte, buf[0]);
+    }
+
+    let _a = writer.await.unwrap();
+    let error = b.read(&mut buf).await.unwrap_err();
+    assert_eq!(IoError::IdleTimedOut.to_string(), error.to_string());
+}
+
+#[tokio::test]
+async fn test_connect_timed_out() {
+    let connect = pending::<anyhow::Result<()>>();
+    let result =
+        Timeout::connect(connect, Timeouts::new().connect(Duration::from_millis(50))).await;
+    assert_eq!(
+        IoError::ConnectTimedOut.to_string(),
+        result.err().unwrap().to_string()
+    );
+}
@@ -0,0 +1,47 @@
+use em_as_net::core::io::io_slice::{IoSlice, IoSliceMut};
+use em_as_net::core::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use em_as_net::core::tcp::adapters::TcpAdapterTokio;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::net::TcpListener;
+
+async fn connect_to_echo_server() -> TcpAdapterTokio {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 64];
+        loop {
+            match stream.read(&mut buf).await.unwrap() {
+                0 => break,
+                n => stream.write_all(&buf[..n]).await.unwrap(),
+            }
+        }
+    });
+
+    TcpAdapterTokio::connect(addr).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_tcp_adapter_tokio_vectored_io() {
+    let mut adapter = connect_to_echo_server().await;
+    assert!(adapter.is_write_vectored());
+    assert!(adapter.is_read_vectored());
+
+    let written = adapter
+        .write_vectored(&[IoSlice::new(b"hello "), IoSlice::new(b"world")])
+        .await
+        .unwrap();
+    assert_eq!(11, written);
+
+    let mut head = [0u8; 6];
+    let mut tail = [0u8; 5];
+    let mut read = 0;
+    while read < 11 {
+        let mut bufs = [IoSliceMut::new(&mut head), IoSliceMut::new(&mut tail)];
+        let mut bufs = &mut bufs[..];
+        IoSliceMut::advance_slices(&mut bufs, read);
+        read += adapter.read_vectored(bufs).await.unwrap();
+    }
+    assert_eq!(b"hello ", &head);
+    assert_eq!(b"world", &tail);
+}
@@ -0,0 +1,8 @@
+mod framed;
+mod io;
+mod pool;
+mod proxy;
+#[cfg(target_os = "linux")]
+mod serial;
+mod tcp;
+mod udp;
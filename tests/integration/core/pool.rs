@@ -0,0 +1,169 @@
+use anyhow::anyhow;
+use em_as_net::core::io::timeout::Duration as EmbassyDuration;
+use em_as_net::core::pool::{FixedPool, HealthCheck, Pool, PoolConfig, PoolError, PoolKey};
+use em_as_net::core::tcp::adapters::TcpAdapterTokio;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+
+/// Accepts connections and keeps them open until told to close them.
+async fn server() -> (SocketAddr, tokio::sync::mpsc::UnboundedReceiver<TcpStream>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            if tx.send(stream).is_err() {
+                break;
+            }
+        }
+    });
+
+    (addr, rx)
+}
+
+async fn connect(addr: SocketAddr, connects: &AtomicUsize) -> anyhow::Result<TcpAdapterTokio> {
+    connects.fetch_add(1, Ordering::Relaxed);
+    TcpAdapterTokio::connect(addr).await
+}
+
+#[tokio::test]
+async fn test_pool_reuses_idle_connections() {
+    let (addr, _accepted) = server().await;
+    let pool = Pool::new(PoolConfig::new());
+    let key = PoolKey::tcp("127.0.0.1".to_string(), addr.port());
+    let connects = AtomicUsize::new(0);
+
+    let connection = pool
+        .get(key.clone(), || connect(addr, &connects))
+        .await
+        .unwrap();
+    let local = connection.get_ref().local_addr().unwrap();
+    assert_eq!(1, pool.active_connections());
+    drop(connection);
+    assert_eq!(0, pool.active_connections());
+    assert_eq!(1, pool.idle_connections());
+
+    let connection = pool
+        .get(key.clone(), || connect(addr, &connects))
+        .await
+        .unwrap();
+    assert_eq!(local, connection.get_ref().local_addr().unwrap());
+    assert_eq!(1, connects.load(Ordering::Relaxed));
+
+    // Other TLS configs don't share connections.
+    let _tls = pool
+        .get(PoolKey::tls(key.host.clone(), key.port, 1), || {
+            connect(addr, &connects)
+        })
+        .await
+        .unwrap();
+    assert_eq!(2, connects.load(Ordering::Relaxed));
+}
+
+#[tokio::test]
+async fn test_pool_health_check_and_idle_timeout() {
+    let (addr, mut accepted) = server().await;
+    let pool = Pool::new(PoolConfig::new().idle_timeout(Some(EmbassyDuration::from_millis(50))));
+    let key = PoolKey::tcp("127.0.0.1".to_string(), addr.port());
+    let connects = AtomicUsize::new(0);
+
+    drop(
+        pool.get(key.clone(), || connect(addr, &connects))
+            .await
+            .unwrap(),
+    );
+    // The server closes the idle connection.
+    drop(accepted.recv().await.unwrap());
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    let mut connection = pool
+        .get(key.clone(), || connect(addr, &connects))
+        .await
+        .unwrap();
+    assert!(connection.get_mut().is_healthy());
+    assert_eq!(2, connects.load(Ordering::Relaxed));
+    drop(connection);
+
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    drop(pool.get(key, || connect(addr, &connects)).await.unwrap());
+    assert_eq!(3, connects.load(Ordering::Relaxed));
+}
+
+#[tokio::test]
+async fn test_pool_limits() {
+    let (addr, _accepted) = server().await;
+    let pool = Pool::new(PoolConfig::new().max_per_host(1).max_idle(1));
+    let a = PoolKey::tcp("a".to_string(), addr.port());
+    let b = PoolKey::tcp("b".to_string(), addr.port());
+    let connects = AtomicUsize::new(0);
+
+    let connection = pool
+        .get(a.clone(), || connect(addr, &connects))
+        .await
+        .unwrap();
+    let error = pool
+        .get(a.clone(), || connect(addr, &connects))
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(PoolError::HostLimitReached.to_string(), error.to_string());
+
+    let other = pool.get(b, || connect(addr, &connects)).await.unwrap();
+    drop(connection);
+    drop(other);
+    assert_eq!(1, pool.idle_connections());
+
+    // A failed connect frees its slot.
+    assert!(pool
+        .get(a.clone(), || async { Err(anyhow!("refused")) })
+        .await
+        .is_err());
+    assert_eq!(0, pool.active_connections());
+}
+
+struct Fake {
+    id: usize,
+    healthy: bool,
+}
+
+impl HealthCheck for Fake {
+    fn is_healthy(&mut self) -> bool {
+        self.healthy
+    }
+}
+
+#[tokio::test]
+async fn test_fixed_pool() {
+    let pool = FixedPool::<Fake, &str, 2>::new(PoolConfig::new());
+    let ids = Arc::new(AtomicUsize::new(0));
+    let fake = || {
+        let ids = ids.clone();
+        async move {
+            Ok(Fake {
+                id: ids.fetch_add(1, Ordering::Relaxed),
+                healthy: true,
+            })
+        }
+    };
+
+    let a = pool.get("a", &fake).await.unwrap();
+    let b = pool.get("b", &fake).await.unwrap();
+    let error = pool.get("c", &fake).await.err().unwrap();
+    assert_eq!(PoolError::PoolFull.to_string(), error.to_string());
+
+    drop(a);
+    // The idle connection of "a" makes room for "c".
+    let c = pool.get("c", &fake).await.unwrap();
+    assert_eq!(2, c.get_ref().id);
+    assert_eq!(0, pool.idle_connections());
+
+    // Unhealthy connections are replaced.
+    let mut b = b;
+    b.get_mut().healthy = false;
+    drop(b);
+    let b = pool.get("b", &fake).await.unwrap();
+    assert_eq!(3, b.detach().id);
+    assert_eq!(1, pool.active_connections());
+}
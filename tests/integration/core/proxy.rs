@@ -0,0 +1,145 @@
+use em_as_net::core::io::{duplex, AsyncReadExt, AsyncWriteExt, DuplexStream};
+use em_as_net::core::proxy::{HttpConnectProxy, ProxyError, ProxyTarget, Socks5Proxy};
+
+/// Reads from `proxy` until the request head is complete.
+async fn read_head(proxy: &mut DuplexStream) -> String {
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        proxy.read_exact(&mut byte).await.unwrap();
+        head.push(byte[0]);
+    }
+
+    String::from_utf8(head).unwrap()
+}
+
+#[tokio::test]
+async fn test_http_connect_with_basic_auth() {
+    let (client, mut proxy) = duplex(1024);
+    let server = tokio::spawn(async move {
+        let head = read_head(&mut proxy).await;
+        proxy
+            .write_all(b"HTTP/1.1 200 Connection established\r\n\r\nhello")
+            .await
+            .unwrap();
+        head
+    });
+
+    let mut tunnel = HttpConnectProxy::new()
+        .basic_auth("user", "pass")
+        .connect(client, ProxyTarget::Domain("example.com", 443))
+        .await
+        .unwrap();
+    let mut buf = [0u8; 5];
+    tunnel.read_exact(&mut buf).await.unwrap();
+    assert_eq!(b"hello", &buf);
+
+    assert_eq!(
+        "CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\
+         Proxy-Authorization: Basic dXNlcjpwYXNz\r\n\r\n",
+        server.await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_http_connect_refused() {
+    let (client, mut proxy) = duplex(1024);
+    tokio::spawn(async move {
+        read_head(&mut proxy).await;
+        proxy
+            .write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n")
+            .await
+            .unwrap();
+    });
+
+    let error = HttpConnectProxy::new()
+        .connect(client, "[::1]:80".parse().map(ProxyTarget::Addr).unwrap())
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(ProxyError::HttpStatus(407).to_string(), error.to_string());
+}
+
+#[tokio::test]
+async fn test_http_connect_rejects_header_injection() {
+    let (client, mut proxy) = duplex(1024);
+
+    let error = HttpConnectProxy::new()
+        .connect(
+            client,
+            ProxyTarget::Domain("example.com\r\nX-Injected: yes", 443),
+        )
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(ProxyError::InvalidHost.to_string(), error.to_string());
+
+    // Nothing was sent to the proxy before the client was dropped.
+    let mut received = Vec::new();
+    proxy.read_to_end(&mut received).await.unwrap();
+    assert!(received.is_empty());
+}
+
+#[tokio::test]
+async fn test_socks5_with_auth_and_remote_dns() {
+    let (client, mut proxy) = duplex(1024);
+    let server = tokio::spawn(async move {
+        let mut greeting = [0u8; 4];
+        proxy.read_exact(&mut greeting).await.unwrap();
+        assert_eq!([5, 2, 0, 2], greeting);
+        proxy.write_all(&[5, 2]).await.unwrap();
+
+        let mut auth = [0u8; 11];
+        proxy.read_exact(&mut auth).await.unwrap();
+        assert_eq!(b"\x01\x04user\x04pass", &auth);
+        proxy.write_all(&[1, 0]).await.unwrap();
+
+        let mut request = [0u8; 18];
+        proxy.read_exact(&mut request).await.unwrap();
+        assert_eq!(b"\x05\x01\x00\x03\x0bexample.com\x01\xbb", &request);
+        proxy
+            .write_all(&[5, 0, 0, 1, 10, 0, 0, 1, 0x1f, 0x90])
+            .await
+            .unwrap();
+        proxy.write_all(b"hello").await.unwrap();
+    });
+
+    let mut tunnel = Socks5Proxy::new()
+        .auth("user", "pass")
+        .connect(client, ProxyTarget::Domain("example.com", 443))
+        .await
+        .unwrap();
+    let mut buf = [0u8; 5];
+    tunnel.read_exact(&mut buf).await.unwrap();
+    assert_eq!(b"hello", &buf);
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_socks5_connect_failed() {
+    let (client, mut proxy) = duplex(1024);
+    tokio::spawn(async move {
+        let mut greeting = [0u8; 3];
+        proxy.read_exact(&mut greeting).await.unwrap();
+        proxy.write_all(&[5, 0]).await.unwrap();
+
+        let mut request = [0u8; 10];
+        proxy.read_exact(&mut request).await.unwrap();
+        assert_eq!([5, 1, 0, 1, 127, 0, 0, 1, 0, 80], request);
+        // Connection refused
+        proxy
+            .write_all(&[5, 5, 0, 1, 0, 0, 0, 0, 0, 0])
+            .await
+            .unwrap();
+    });
+
+    let error = Socks5Proxy::new()
+        .connect(
+            client,
+            ProxyTarget::from("127.0.0.1:80".parse::<std::net::SocketAddr>().unwrap()),
+        )
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(ProxyError::ConnectFailed(5).to_string(), error.to_string());
+}
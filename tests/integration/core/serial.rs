@@ -0,0 +1,54 @@
+use em_as_net::core::io::{AsyncReadExt, AsyncWriteExt};
+use em_as_net::core::serial::{Parity, SerialAdapterTokio, SerialConfig};
+use std::ffi::CStr;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::os::unix::io::FromRawFd;
+
+/// Opens a pseudo-terminal pair, returning the master side and the path of the slave tty.
+fn open_pty() -> (File, String) {
+    unsafe {
+        let master = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+        assert!(master >= 0);
+        assert_eq!(0, libc::grantpt(master));
+        assert_eq!(0, libc::unlockpt(master));
+
+        let mut name = [0 as libc::c_char; 128];
+        assert_eq!(0, libc::ptsname_r(master, name.as_mut_ptr(), name.len()));
+        let path = CStr::from_ptr(name.as_ptr()).to_str().unwrap().to_owned();
+
+        (File::from_raw_fd(master), path)
+    }
+}
+
+#[tokio::test]
+async fn test_serial_adapter_over_pty() {
+    let (mut master, path) = open_pty();
+    let mut serial =
+        SerialAdapterTokio::open(&path, SerialConfig::new(9600).parity(Parity::Even)).unwrap();
+
+    master.write_all(b"AT\r").unwrap();
+    let mut command = [0u8; 3];
+    serial.read_exact(&mut command).await.unwrap();
+    assert_eq!(b"AT\r", &command);
+
+    serial.write_all(b"OK\r\n").await.unwrap();
+    serial.flush().await.unwrap();
+    let response = tokio::task::spawn_blocking(move || {
+        let mut response = [0u8; 4];
+        master.read_exact(&mut response).unwrap();
+        response
+    })
+    .await
+    .unwrap();
+    assert_eq!(b"OK\r\n", &response);
+}
+
+#[test]
+fn test_serial_adapter_rejects_unsupported_baud_rate() {
+    let (_master, path) = open_pty();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let _guard = runtime.enter();
+
+    assert!(SerialAdapterTokio::open(path, SerialConfig::new(12_345)).is_err());
+}
@@ -0,0 +1,233 @@
+use em_as_net::core::framed::IoError;
+use em_as_net::core::io::timeout::Duration as EmbassyDuration;
+use em_as_net::core::io::{AsyncReadExt, AsyncWriteExt};
+use em_as_net::core::tcp::adapters::TcpAdapterTokio;
+use em_as_net::core::tcp::listeners::TcpListenerTokio;
+#[cfg(target_os = "linux")]
+use em_as_net::core::tcp::TcpState;
+use em_as_net::core::tcp::{KeepAlive, TcpOptions};
+#[cfg(target_os = "linux")]
+use std::os::unix::io::AsRawFd;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::net::{TcpSocket, TcpStream};
+
+#[tokio::test]
+async fn test_tcp_listener_tokio_accept() {
+    let listener = TcpListenerTokio::bind("127.0.0.1:0", 4).await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let client = tokio::spawn(async move {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(b"hello").await.unwrap();
+        let mut buf = [0u8; 5];
+        stream.read_exact(&mut buf).await.unwrap();
+        buf
+    });
+
+    let (mut connection, _) = listener.accept().await.unwrap();
+    let mut buf = [0u8; 5];
+    connection.read_exact(&mut buf).await.unwrap();
+    connection.write_all(&buf).await.unwrap();
+
+    assert_eq!(b"hello", &client.await.unwrap());
+    assert_eq!(1, listener.active_connections());
+}
+
+#[tokio::test]
+async fn test_tcp_listener_tokio_connection_limit() {
+    let listener = TcpListenerTokio::bind("127.0.0.1:0", 1).await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let _first_client = TcpStream::connect(addr).await.unwrap();
+    let _second_client = TcpStream::connect(addr).await.unwrap();
+
+    let (first, _) = listener.accept().await.unwrap();
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), listener.accept())
+            .await
+            .is_err()
+    );
+
+    drop(first);
+    let (_second, _) = tokio::time::timeout(Duration::from_secs(1), listener.accept())
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+/// Reads an integer socket option of `adapter`.
+#[cfg(target_os = "linux")]
+fn socket_option(adapter: &TcpAdapterTokio, level: libc::c_int, name: libc::c_int) -> libc::c_int {
+    let mut value: libc::c_int = 0;
+    let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+    let result = unsafe {
+        libc::getsockopt(
+            adapter.get_ref().as_raw_fd(),
+            level,
+            name,
+            &mut value as *mut libc::c_int as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    assert_eq!(0, result);
+
+    value
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_tcp_adapter_tokio_options() {
+    let listener = TcpListenerTokio::bind("127.0.0.1:0", 1).await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let options = TcpOptions::new()
+        .keep_alive(Some(
+            KeepAlive::new(EmbassyDuration::from_secs(30))
+                .interval(EmbassyDuration::from_secs(5))
+                .count(3),
+        ))
+        .nodelay(true)
+        .send_buffer_size(16 * 1024)
+        .recv_buffer_size(16 * 1024)
+        .linger(Some(EmbassyDuration::from_secs(1)))
+        .hop_limit(32)
+        .timeout(Some(EmbassyDuration::from_secs(10)));
+    let mut adapter = TcpAdapterTokio::connect_with_options(addr, &options)
+        .await
+        .unwrap();
+    let (mut connection, _) = listener.accept().await.unwrap();
+
+    assert_eq!(
+        1,
+        socket_option(&adapter, libc::SOL_SOCKET, libc::SO_KEEPALIVE)
+    );
+    assert_eq!(
+        30,
+        socket_option(&adapter, libc::IPPROTO_TCP, libc::TCP_KEEPIDLE)
+    );
+    assert_eq!(
+        5,
+        socket_option(&adapter, libc::IPPROTO_TCP, libc::TCP_KEEPINTVL)
+    );
+    assert_eq!(
+        3,
+        socket_option(&adapter, libc::IPPROTO_TCP, libc::TCP_KEEPCNT)
+    );
+    assert_eq!(
+        10_000,
+        socket_option(&adapter, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT)
+    );
+    assert_eq!(
+        1,
+        socket_option(&adapter, libc::IPPROTO_TCP, libc::TCP_NODELAY)
+    );
+    // Linux doubles the requested buffer sizes for its bookkeeping.
+    assert!(socket_option(&adapter, libc::SOL_SOCKET, libc::SO_SNDBUF) >= 16 * 1024);
+    assert!(socket_option(&adapter, libc::SOL_SOCKET, libc::SO_RCVBUF) >= 16 * 1024);
+    assert!(adapter.get_ref().nodelay().unwrap());
+    assert_eq!(
+        Some(Duration::from_secs(1)),
+        adapter.get_ref().linger().unwrap()
+    );
+    assert_eq!(32, adapter.get_ref().ttl().unwrap());
+
+    adapter
+        .set_options(&TcpOptions::new().nodelay(false).keep_alive(None))
+        .unwrap();
+    assert!(!adapter.get_ref().nodelay().unwrap());
+    assert_eq!(
+        0,
+        socket_option(&adapter, libc::SOL_SOCKET, libc::SO_KEEPALIVE)
+    );
+    // Untouched options keep their value.
+    assert_eq!(32, adapter.get_ref().ttl().unwrap());
+
+    adapter.write_all(b"ok").await.unwrap();
+    let mut buf = [0u8; 2];
+    connection.read_exact(&mut buf).await.unwrap();
+    assert_eq!(b"ok", &buf);
+}
+
+#[tokio::test]
+async fn test_tcp_adapter_tokio_set_options() {
+    let listener = TcpListenerTokio::bind("127.0.0.1:0", 1).await.unwrap();
+    let adapter = TcpAdapterTokio::connect(listener.local_addr().unwrap())
+        .await
+        .unwrap();
+
+    adapter
+        .set_options(
+            &TcpOptions::new()
+                .keep_alive(Some(KeepAlive::new(EmbassyDuration::from_secs(30))))
+                .nodelay(true)
+                .linger(None)
+                .hop_limit(16),
+        )
+        .unwrap();
+
+    assert!(adapter.get_ref().nodelay().unwrap());
+    assert_eq!(None, adapter.get_ref().linger().unwrap());
+    assert_eq!(16, adapter.get_ref().ttl().unwrap());
+}
+
+#[tokio::test]
+async fn test_tcp_adapter_tokio_half_close() {
+    let listener = TcpListenerTokio::bind("127.0.0.1:0", 1).await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut client = TcpAdapterTokio::connect(addr).await.unwrap();
+    let (mut server, _) = listener.accept().await.unwrap();
+
+    assert_eq!(addr, client.peer_addr().unwrap());
+    assert_eq!(
+        client.local_addr().unwrap(),
+        server.get_ref().peer_addr().unwrap()
+    );
+    #[cfg(target_os = "linux")]
+    assert_eq!(TcpState::Established, client.state().unwrap());
+
+    client.write_all(b"upload").await.unwrap();
+    client.shutdown_write().unwrap();
+
+    let mut upload = Vec::new();
+    server.read_to_end(&mut upload).await.unwrap();
+    assert_eq!(b"upload", &upload[..]);
+    #[cfg(target_os = "linux")]
+    assert_eq!(TcpState::CloseWait, server.get_ref().state().unwrap());
+
+    // The client can still read after closing its write side.
+    server.write_all(b"done").await.unwrap();
+    let mut buf = [0u8; 4];
+    client.read_exact(&mut buf).await.unwrap();
+    assert_eq!(b"done", &buf);
+}
+
+#[tokio::test]
+async fn test_tcp_adapter_tokio_connect_timed_out() {
+    // The accept queue is never drained. Once it is full, further SYNs are dropped and
+    // connecting hangs.
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    let listener = socket.listen(1).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let mut connections = Vec::new();
+    let error = loop {
+        match TcpAdapterTokio::connect_with_timeout(addr, EmbassyDuration::from_millis(200)).await {
+            Ok(connection) => connections.push(connection),
+            Err(error) => break error,
+        }
+        assert!(connections.len() < 16, "the accept queue never filled up");
+    };
+    assert_eq!(IoError::ConnectTimedOut.to_string(), error.to_string());
+}
+
+#[cfg(not(target_os = "linux"))]
+#[tokio::test]
+async fn test_tcp_adapter_tokio_state_unsupported() {
+    let listener = TcpListenerTokio::bind("127.0.0.1:0", 1).await.unwrap();
+    let client = TcpAdapterTokio::connect(listener.local_addr().unwrap())
+        .await
+        .unwrap();
+
+    assert!(client.state().is_err());
+}
@@ -0,0 +1,52 @@
+use em_as_net::core::framed::Codec;
+use em_as_net::core::udp::adapters::UdpAdapterTokio;
+use em_as_net::core::udp::{UdpFramed, UdpSocket};
+
+#[tokio::test]
+async fn test_udp_adapter_tokio_connected() {
+    let mut a = UdpAdapterTokio::bind("127.0.0.1:0").await.unwrap();
+    let mut b = UdpAdapterTokio::bind("127.0.0.1:0").await.unwrap();
+    a.connect(b.local_addr().unwrap()).await.unwrap();
+    b.connect(a.local_addr().unwrap()).await.unwrap();
+
+    assert_eq!(4, a.send(b"ping").await.unwrap());
+    let mut buf = [0u8; 16];
+    let size = b.recv(&mut buf).await.unwrap();
+    assert_eq!(b"ping", &buf[..size]);
+}
+
+#[tokio::test]
+async fn test_udp_framed_one_frame_per_datagram() {
+    let a = UdpAdapterTokio::bind("127.0.0.1:0").await.unwrap();
+    let b = UdpAdapterTokio::bind("127.0.0.1:0").await.unwrap();
+    let a_addr = a.local_addr().unwrap();
+    let b_addr = b.local_addr().unwrap();
+    let mut a = UdpFramed::new(a, Codec::new());
+    let mut b = UdpFramed::new(b, Codec::new());
+
+    a.send(&b"first"[..], b_addr).await.unwrap();
+    a.send(&b"second"[..], b_addr).await.unwrap();
+
+    let (frame, from) = b.recv().await.unwrap();
+    assert_eq!(b"first", &frame[..]);
+    assert_eq!(a_addr, from);
+    let (frame, _) = b.recv().await.unwrap();
+    assert_eq!(b"second", &frame[..]);
+}
+
+#[tokio::test]
+async fn test_udp_adapter_tokio_rebind() {
+    let mut a = UdpAdapterTokio::bind("127.0.0.1:0").await.unwrap();
+    let b = UdpAdapterTokio::bind("127.0.0.1:0").await.unwrap();
+    let old_addr = a.local_addr().unwrap();
+
+    a.rebind("127.0.0.1:0".parse().unwrap()).await.unwrap();
+    let new_addr = a.local_addr().unwrap();
+    assert_ne!(old_addr, new_addr);
+
+    b.send_to(b"ping", new_addr).await.unwrap();
+    let mut buf = [0u8; 16];
+    let (size, from) = a.recv_from(&mut buf).await.unwrap();
+    assert_eq!(b"ping", &buf[..size]);
+    assert_eq!(b.local_addr().unwrap(), from);
+}
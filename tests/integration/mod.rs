@@ -1 +1,2 @@
 mod clients;
+mod core;